@@ -1,5 +1,9 @@
 use super::*;
 
+use std::path::PathBuf;
+
+const CALLBACK_BASE_ID: i32 = 1000;
+
 /// An id for a steam app/game
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -10,10 +14,20 @@ impl From<u32> for AppId {
     }
 }
 
+/// An id for a depot, a set of files that make up part of an app
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DepotId(pub u32);
+impl From<u32> for DepotId {
+    fn from(id: u32) -> Self {
+        DepotId(id)
+    }
+}
+
 /// Access to the steam apps interface
 pub struct Apps<Manager> {
     pub(crate) apps: *mut sys::ISteamApps,
-    pub(crate) _inner: Arc<Inner<Manager>>,
+    pub(crate) inner: Arc<Inner<Manager>>,
 }
 
 impl<Manager> Apps<Manager> {
@@ -45,6 +59,13 @@ impl<Manager> Apps<Manager> {
         unsafe { sys::SteamAPI_ISteamApps_BIsSubscribedFromFreeWeekend(self.apps) }
     }
 
+    /// Returns whether the user borrowed the app through Steam Family Sharing
+    ///
+    /// Use `app_owner` to find out who lent it.
+    pub fn is_subscribed_from_family_sharing(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamApps_BIsSubscribedFromFamilySharing(self.apps) }
+    }
+
     /// Returns whether the user has a VAC ban on their account.
     pub fn is_vac_banned(&self) -> bool {
         unsafe { sys::SteamAPI_ISteamApps_BIsVACBanned(self.apps) }
@@ -67,7 +88,21 @@ impl<Manager> Apps<Manager> {
         unsafe { sys::SteamAPI_ISteamApps_BIsSubscribed(self.apps) }
     }
 
+    /// Returns when the user first purchased the app with the given ID, as
+    /// seconds since the unix epoch.
+    ///
+    /// Returns 0 if the user doesn't own the app. Useful for rewarding
+    /// long-time players.
+    pub fn earliest_purchase_unix_time(&self, app_id: AppId) -> u32 {
+        unsafe { sys::SteamAPI_ISteamApps_GetEarliestPurchaseUnixTime(self.apps, app_id.0) }
+    }
+
     /// Returns the build id of this app.
+    ///
+    /// This is the id of the content build the user has installed, which can
+    /// change while the game is running if the user opted into a beta branch.
+    /// It is useful for crash reports and for checking that clients and
+    /// servers run compatible builds.
     pub fn app_build_id(&self) -> i32 {
         unsafe { sys::SteamAPI_ISteamApps_GetAppBuildId(self.apps) as i32 }
     }
@@ -75,8 +110,9 @@ impl<Manager> Apps<Manager> {
     /// Returns the installation folder of the app with the given ID.
     ///
     /// This works even if the app isn't installed, returning where it
-    /// would be installed in the default location.
-    pub fn app_install_dir(&self, app_id: AppId) -> String {
+    /// would be installed in the default location. Use `is_app_installed`
+    /// to check whether it actually exists.
+    pub fn app_install_dir(&self, app_id: AppId) -> PathBuf {
         unsafe {
             let mut buffer = vec![0; 2048];
             sys::SteamAPI_ISteamApps_GetAppInstallDir(
@@ -86,13 +122,57 @@ impl<Manager> Apps<Manager> {
                 buffer.len() as u32,
             );
             let path = CStr::from_ptr(buffer.as_ptr());
-            path.to_string_lossy().into_owned()
+            PathBuf::from(path.to_string_lossy().into_owned())
+        }
+    }
+
+    /// Flags the app's installation as damaged so that steam verifies and
+    /// repairs it the next time the game is closed.
+    ///
+    /// If `missing_files_only` is set only missing files are redownloaded,
+    /// otherwise every file is checked. Returns whether the request was
+    /// accepted.
+    pub fn mark_content_corrupt(&self, missing_files_only: bool) -> bool {
+        unsafe { sys::SteamAPI_ISteamApps_MarkContentCorrupt(self.apps, missing_files_only) }
+    }
+
+    /// Requests the size and SHA1 hash of a file in the app's install
+    /// directory as listed in the depot manifest.
+    ///
+    /// Comparing the hash against the file on disk detects files that were
+    /// modified or damaged after they were installed.
+    pub fn file_details<F>(&self, path: &str, cb: F)
+    where
+        F: FnOnce(Result<FileDetails, SteamError>) + 'static + Send,
+    {
+        let path = CString::new(path).unwrap();
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamApps_GetFileDetails(self.apps, path.as_ptr());
+            register_call_result::<sys::FileDetailsResult_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 23,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(FileDetails {
+                            size: v.m_ulFileSize,
+                            sha1: v.m_FileSHA,
+                            flags: v.m_unFlags,
+                        })
+                    })
+                },
+            );
         }
     }
 
     /// Returns the steam id of the original owner of the app.
     ///
-    /// Differs from the current user if the app is borrowed.
+    /// Differs from the current user if the app is borrowed. This matches
+    /// the `owner_steam_id` reported by `ValidateAuthTicketResponse`.
     pub fn app_owner(&self) -> SteamId {
         unsafe { SteamId(sys::SteamAPI_ISteamApps_GetAppOwner(self.apps)) }
     }
@@ -103,7 +183,11 @@ impl<Manager> Apps<Manager> {
             let langs = sys::SteamAPI_ISteamApps_GetAvailableGameLanguages(self.apps);
             let langs = CStr::from_ptr(langs);
             let langs = langs.to_string_lossy();
-            langs.split(',').map(|v| v.to_owned()).collect()
+            langs
+                .split(',')
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_owned())
+                .collect()
         }
     }
 
@@ -119,10 +203,40 @@ impl<Manager> Apps<Manager> {
         }
     }
 
+    /// Returns the depots of the app with the given ID that are installed,
+    /// in mount order.
+    ///
+    /// Optional depots, e.g. language packs, only show up here if the user
+    /// selected them.
+    pub fn installed_depots(&self, app_id: AppId) -> Vec<DepotId> {
+        let mut depots = vec![0; 64];
+        loop {
+            let count = unsafe {
+                sys::SteamAPI_ISteamApps_GetInstalledDepots(
+                    self.apps,
+                    app_id.0,
+                    depots.as_mut_ptr(),
+                    depots.len() as u32,
+                )
+            } as usize;
+            // Steam stops once the buffer is full, so there may be more depots
+            if count < depots.len() {
+                depots.truncate(count);
+                return depots.into_iter().map(DepotId).collect();
+            }
+            depots = vec![0; depots.len() * 2];
+        }
+    }
+
     /// Returns the current beta name if any.
     ///
     /// If the user isn't playing on a beta branch then this
     /// returns `None`
+    ///
+    /// Listing and switching branches (`GetNumBetas`, `GetBetaInfo` and
+    /// `SetActiveBeta`) isn't supported yet: the SDK `steamworks-sys` is
+    /// generated from and the bundled `steam_api` libraries don't contain
+    /// these calls.
     pub fn current_beta_name(&self) -> Option<String> {
         unsafe {
             let mut buffer = vec![0; 256];
@@ -138,4 +252,69 @@ impl<Manager> Apps<Manager> {
             }
         }
     }
+
+    /// Returns the value of the given key from the parameters the game was
+    /// launched with through a `steam://run/<appid>//?param=value` link.
+    ///
+    /// Returns `None` if the key wasn't set. Keys starting with `@` are
+    /// reserved by steam and always return `None`.
+    pub fn launch_query_param(&self, key: &str) -> Option<String> {
+        let key = CString::new(key).unwrap();
+        unsafe {
+            let value = sys::SteamAPI_ISteamApps_GetLaunchQueryParam(self.apps, key.as_ptr());
+            let value = CStr::from_ptr(value).to_string_lossy();
+            if value.is_empty() {
+                None
+            } else {
+                Some(value.into_owned())
+            }
+        }
+    }
+
+    /// Returns the command line the game was launched with through a
+    /// `steam://run/<appid>//<command line>` link.
+    ///
+    /// This is empty if the game wasn't launched through such a link.
+    /// Check it again when a `NewUrlLaunchParameters` callback arrives.
+    pub fn launch_command_line(&self) -> String {
+        unsafe {
+            let mut buffer = vec![0; 2048];
+            sys::SteamAPI_ISteamApps_GetLaunchCommandLine(
+                self.apps,
+                buffer.as_mut_ptr(),
+                buffer.len() as _,
+            );
+            let command_line = CStr::from_ptr(buffer.as_ptr());
+            command_line.to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// The details of a file in the app's depots, see `Apps::file_details`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FileDetails {
+    /// The size of the file in bytes
+    pub size: u64,
+    /// The SHA1 hash of the file's contents
+    pub sha1: [u8; 20],
+    /// The flags of the file in the depot manifest
+    pub flags: u32,
+}
+
+/// Called when the game is already running and the user follows a
+/// `steam://run/<appid>//` link with new launch parameters.
+///
+/// Use `Apps::launch_query_param` or `Apps::launch_command_line` to read them.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NewUrlLaunchParameters;
+
+unsafe impl Callback for NewUrlLaunchParameters {
+    const ID: i32 = CALLBACK_BASE_ID + 14;
+    const SIZE: i32 = ::std::mem::size_of::<sys::NewUrlLaunchParameters_t>() as i32;
+
+    unsafe fn from_raw(_: *mut c_void) -> Self {
+        NewUrlLaunchParameters
+    }
 }
@@ -73,3 +73,62 @@ pub(crate) unsafe fn register_call_result<C, F, Manager>(
         Box::new(move |param, failed| f(&*(param as *const C), failed)),
     );
 }
+
+/// A future that resolves to the result of an asynchronous steam call.
+///
+/// Results are only delivered while `SingleClient::run_callbacks` is being called,
+/// so the future will never complete if the callbacks aren't run.
+#[cfg(feature = "futures")]
+pub struct CallResultFuture<T> {
+    shared: Arc<Mutex<CallResultShared<T>>>,
+}
+
+#[cfg(feature = "futures")]
+struct CallResultShared<T> {
+    result: Option<T>,
+    waker: Option<std::task::Waker>,
+}
+
+#[cfg(feature = "futures")]
+impl<T> CallResultFuture<T> {
+    /// Creates a pending future and the function that completes it
+    pub(crate) fn new() -> (CallResultFuture<T>, impl FnOnce(T) + Send + 'static)
+    where
+        T: Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(CallResultShared {
+            result: None,
+            waker: None,
+        }));
+        let complete = {
+            let shared = shared.clone();
+            move |result| {
+                let waker = {
+                    let mut shared = shared.lock().unwrap();
+                    shared.result = Some(result);
+                    shared.waker.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        };
+        (CallResultFuture { shared }, complete)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> std::future::Future for CallResultFuture<T> {
+    type Output = T;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<T> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.result.take() {
+            Some(result) => std::task::Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        }
+    }
+}
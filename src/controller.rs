@@ -0,0 +1,184 @@
+use super::*;
+
+/// Access to the legacy steam controller interface.
+///
+/// New games should use `Input` instead, this is only for games whose
+/// configurations still target `ISteamController`.
+pub struct Controller<Manager> {
+    pub(crate) controller: *mut sys::ISteamController,
+    pub(crate) _inner: Arc<Inner<Manager>>,
+}
+
+impl<Manager> Controller<Manager> {
+    /// Init must be called when starting use of this interface.
+    ///
+    /// Returns whether the interface was initialized.
+    pub fn init(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamController_Init(self.controller) }
+    }
+
+    /// Shutdown must be called when ending use of this interface.
+    pub fn shutdown(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamController_Shutdown(self.controller) }
+    }
+
+    /// Synchronize API state with the latest controller data available.
+    ///
+    /// `SingleClient::run_callbacks` does this as well.
+    pub fn run_frame(&self) {
+        unsafe { sys::SteamAPI_ISteamController_RunFrame(self.controller) }
+    }
+
+    /// Returns a list of the currently connected controllers
+    pub fn get_connected_controllers(&self) -> Vec<sys::ControllerHandle_t> {
+        unsafe {
+            let mut handles = vec![0_u64; sys::STEAM_CONTROLLER_MAX_COUNT as usize];
+            let quantity = sys::SteamAPI_ISteamController_GetConnectedControllers(
+                self.controller,
+                handles.as_mut_ptr(),
+            );
+            handles.truncate(quantity as usize);
+            handles
+        }
+    }
+
+    /// Returns the handle of the action set with the given name
+    pub fn get_action_set_handle(&self, action_set_name: &str) -> sys::ControllerActionSetHandle_t {
+        let name = CString::new(action_set_name).unwrap();
+        unsafe { sys::SteamAPI_ISteamController_GetActionSetHandle(self.controller, name.as_ptr()) }
+    }
+
+    /// Reconfigure the controller to use the specified action set
+    pub fn activate_action_set_handle(
+        &self,
+        controller_handle: sys::ControllerHandle_t,
+        action_set_handle: sys::ControllerActionSetHandle_t,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamController_ActivateActionSet(
+                self.controller,
+                controller_handle,
+                action_set_handle,
+            )
+        }
+    }
+
+    /// Get the handle of the specified Digital action.
+    pub fn get_digital_action_handle(
+        &self,
+        action_name: &str,
+    ) -> sys::ControllerDigitalActionHandle_t {
+        let name = CString::new(action_name).unwrap();
+        unsafe {
+            sys::SteamAPI_ISteamController_GetDigitalActionHandle(self.controller, name.as_ptr())
+        }
+    }
+
+    /// Get the handle of the specified Analog action.
+    pub fn get_analog_action_handle(
+        &self,
+        action_name: &str,
+    ) -> sys::ControllerAnalogActionHandle_t {
+        let name = CString::new(action_name).unwrap();
+        unsafe {
+            sys::SteamAPI_ISteamController_GetAnalogActionHandle(self.controller, name.as_ptr())
+        }
+    }
+
+    /// Returns the current state of the supplied digital game action.
+    pub fn get_digital_action_data(
+        &self,
+        controller_handle: sys::ControllerHandle_t,
+        action_handle: sys::ControllerDigitalActionHandle_t,
+    ) -> DigitalActionData {
+        unsafe {
+            let data = sys::SteamAPI_ISteamController_GetDigitalActionData(
+                self.controller,
+                controller_handle,
+                action_handle,
+            );
+            DigitalActionData {
+                state: data.bState,
+                active: data.bActive,
+            }
+        }
+    }
+
+    /// Returns the current state of the supplied analog game action.
+    pub fn get_analog_action_data(
+        &self,
+        controller_handle: sys::ControllerHandle_t,
+        action_handle: sys::ControllerAnalogActionHandle_t,
+    ) -> AnalogActionData {
+        unsafe {
+            let data = sys::SteamAPI_ISteamController_GetAnalogActionData(
+                self.controller,
+                controller_handle,
+                action_handle,
+            );
+            AnalogActionData {
+                mode: data.eMode.into(),
+                x: data.x,
+                y: data.y,
+                active: data.bActive,
+            }
+        }
+    }
+
+    /// Plays a single haptic pulse on a Steam Controller trackpad
+    pub fn trigger_haptic_pulse(
+        &self,
+        controller_handle: sys::ControllerHandle_t,
+        pad: ControllerPad,
+        duration_micros: u16,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamController_TriggerHapticPulse(
+                self.controller,
+                controller_handle,
+                pad.into(),
+                duration_micros,
+            )
+        }
+    }
+
+    /// Plays `repeat` haptic pulses on a Steam Controller trackpad, each
+    /// followed by a pause of `off_micros`
+    pub fn trigger_repeated_haptic_pulse(
+        &self,
+        controller_handle: sys::ControllerHandle_t,
+        pad: ControllerPad,
+        duration_micros: u16,
+        off_micros: u16,
+        repeat: u16,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamController_TriggerRepeatedHapticPulse(
+                self.controller,
+                controller_handle,
+                pad.into(),
+                duration_micros,
+                off_micros,
+                repeat,
+                0,
+            )
+        }
+    }
+}
+
+/// A trackpad of the Steam Controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ControllerPad {
+    Left,
+    Right,
+}
+
+impl From<ControllerPad> for sys::ESteamControllerPad {
+    fn from(pad: ControllerPad) -> Self {
+        match pad {
+            ControllerPad::Left => sys::ESteamControllerPad::k_ESteamControllerPad_Left,
+            ControllerPad::Right => sys::ESteamControllerPad::k_ESteamControllerPad_Right,
+        }
+    }
+}
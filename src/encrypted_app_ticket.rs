@@ -0,0 +1,125 @@
+use super::*;
+
+/// An encrypted app ticket decrypted with the app's secret key.
+///
+/// Encrypted app tickets are requested by the client with
+/// `User::request_encrypted_app_ticket` and can be verified by a backend
+/// without contacting steam. The key can be found on the app's 'SDK Auth'
+/// page in the Steamworks partner site and must never be shipped to players.
+pub struct DecryptedAppTicket {
+    ticket: Vec<u8>,
+}
+
+impl DecryptedAppTicket {
+    /// Decrypts the ticket with the given key.
+    ///
+    /// Returns `None` if the ticket couldn't be decrypted, e.g. because it
+    /// was tampered with or the key is wrong.
+    pub fn decrypt(
+        encrypted_ticket: &[u8],
+        key: &[u8; sys::k_nSteamEncryptedAppTicketSymmetricKeyLen as usize],
+    ) -> Option<DecryptedAppTicket> {
+        unsafe {
+            let mut ticket = vec![0; 1024];
+            let mut ticket_len = ticket.len() as u32;
+            if sys::SteamEncryptedAppTicket_BDecryptTicket(
+                encrypted_ticket.as_ptr(),
+                encrypted_ticket.len() as u32,
+                ticket.as_mut_ptr(),
+                &mut ticket_len,
+                key.as_ptr(),
+                key.len() as _,
+            ) {
+                ticket.truncate(ticket_len as usize);
+                Some(DecryptedAppTicket { ticket })
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns whether the ticket was issued for the given app
+    pub fn is_for_app(&self, app_id: AppId) -> bool {
+        unsafe {
+            sys::SteamEncryptedAppTicket_BIsTicketForApp(
+                // The functions only read the ticket despite taking a mutable pointer
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+                app_id.0,
+            )
+        }
+    }
+
+    /// Returns the app the ticket was issued for
+    pub fn app_id(&self) -> AppId {
+        unsafe {
+            AppId(sys::SteamEncryptedAppTicket_GetTicketAppID(
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+            ))
+        }
+    }
+
+    /// Returns the user the ticket was issued to
+    pub fn steam_id(&self) -> SteamId {
+        unsafe {
+            let mut steam_id = sys::CSteamID {
+                m_steamid: sys::CSteamID_SteamID_t { m_unAll64Bits: 0 },
+            };
+            sys::SteamEncryptedAppTicket_GetTicketSteamID(
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+                &mut steam_id,
+            );
+            SteamId(steam_id.m_steamid.m_unAll64Bits)
+        }
+    }
+
+    /// Returns when the ticket was issued, as seconds since the unix epoch
+    pub fn issue_time(&self) -> u32 {
+        unsafe {
+            sys::SteamEncryptedAppTicket_GetTicketIssueTime(
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+            )
+        }
+    }
+
+    /// Returns whether the user owns the given app, e.g. a dlc
+    pub fn user_owns_app(&self, app_id: AppId) -> bool {
+        unsafe {
+            sys::SteamEncryptedAppTicket_BUserOwnsAppInTicket(
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+                app_id.0,
+            )
+        }
+    }
+
+    /// Returns whether the user has a VAC ban on their account
+    pub fn is_user_vac_banned(&self) -> bool {
+        unsafe {
+            sys::SteamEncryptedAppTicket_BUserIsVacBanned(
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+            )
+        }
+    }
+
+    /// Returns the data the game passed to `User::request_encrypted_app_ticket`
+    pub fn user_data(&self) -> Vec<u8> {
+        unsafe {
+            let mut data_len = 0;
+            let data = sys::SteamEncryptedAppTicket_GetUserVariableData(
+                self.ticket.as_ptr() as *mut u8,
+                self.ticket.len() as u32,
+                &mut data_len,
+            );
+            if data.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(data, data_len as usize).to_vec()
+            }
+        }
+    }
+}
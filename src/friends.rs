@@ -267,21 +267,7 @@ impl<Manager> Friend<Manager> {
         unsafe {
             let utils = sys::SteamAPI_SteamUtils_v010();
             let img = sys::SteamAPI_ISteamFriends_GetSmallFriendAvatar(self.friends, self.id.0);
-            if img == 0 {
-                return None;
-            }
-            let mut width = 0;
-            let mut height = 0;
-            if !sys::SteamAPI_ISteamUtils_GetImageSize(utils, img, &mut width, &mut height) {
-                return None;
-            }
-            assert_eq!(width, 32);
-            assert_eq!(height, 32);
-            let mut dest = vec![0; 32 * 32 * 4];
-            if !sys::SteamAPI_ISteamUtils_GetImageRGBA(utils, img, dest.as_mut_ptr(), 32 * 32 * 4) {
-                return None;
-            }
-            Some(dest)
+            load_image(utils, img).map(|img| img.rgba)
         }
     }
 
@@ -290,21 +276,7 @@ impl<Manager> Friend<Manager> {
         unsafe {
             let utils = sys::SteamAPI_SteamUtils_v010();
             let img = sys::SteamAPI_ISteamFriends_GetMediumFriendAvatar(self.friends, self.id.0);
-            if img == 0 {
-                return None;
-            }
-            let mut width = 0;
-            let mut height = 0;
-            if !sys::SteamAPI_ISteamUtils_GetImageSize(utils, img, &mut width, &mut height) {
-                return None;
-            }
-            assert_eq!(width, 64);
-            assert_eq!(height, 64);
-            let mut dest = vec![0; 64 * 64 * 4];
-            if !sys::SteamAPI_ISteamUtils_GetImageRGBA(utils, img, dest.as_mut_ptr(), 64 * 64 * 4) {
-                return None;
-            }
-            Some(dest)
+            load_image(utils, img).map(|img| img.rgba)
         }
     }
 
@@ -313,22 +285,7 @@ impl<Manager> Friend<Manager> {
         unsafe {
             let utils = sys::SteamAPI_SteamUtils_v010();
             let img = sys::SteamAPI_ISteamFriends_GetLargeFriendAvatar(self.friends, self.id.0);
-            if img == 0 {
-                return None;
-            }
-            let mut width = 0;
-            let mut height = 0;
-            if !sys::SteamAPI_ISteamUtils_GetImageSize(utils, img, &mut width, &mut height) {
-                return None;
-            }
-            assert_eq!(width, 184);
-            assert_eq!(height, 184);
-            let mut dest = vec![0; 184 * 184 * 4];
-            if !sys::SteamAPI_ISteamUtils_GetImageRGBA(utils, img, dest.as_mut_ptr(), 184 * 184 * 4)
-            {
-                return None;
-            }
-            Some(dest)
+            load_image(utils, img).map(|img| img.rgba)
         }
     }
 }
@@ -0,0 +1,631 @@
+use super::*;
+
+use std::path::Path;
+use std::time::SystemTime;
+
+const CALLBACK_BASE_ID: i32 = 4500;
+
+/// Access to the steam HTML surface interface
+///
+/// The HTML surface renders web pages offscreen so that they can be drawn
+/// as a texture in game. Pages are delivered through the `HtmlNeedsPaint`
+/// callback.
+pub struct HtmlSurface<Manager> {
+    pub(crate) html: *mut sys::ISteamHTMLSurface,
+    pub(crate) inner: Arc<Inner<Manager>>,
+}
+
+/// A handle to a browser created with `HtmlSurface::create_browser`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BrowserHandle(pub u32);
+
+impl<Manager> HtmlSurface<Manager> {
+    /// Initializes the HTML surface, this must be called before creating
+    /// any browsers.
+    ///
+    /// Returns whether the surface was initialized.
+    pub fn init(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamHTMLSurface_Init(self.html) }
+    }
+
+    /// Shuts down the HTML surface once it is no longer needed
+    pub fn shutdown(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamHTMLSurface_Shutdown(self.html) }
+    }
+
+    /// Creates a new browser, calling the callback with its handle once it
+    /// is ready to load pages.
+    ///
+    /// The user agent is appended to the default steam one and the user css
+    /// is applied to every page loaded by the browser.
+    pub fn create_browser<F>(&self, user_agent: Option<&str>, user_css: Option<&str>, cb: F)
+    where
+        F: FnOnce(Result<BrowserHandle, SteamError>) + 'static + Send,
+    {
+        let user_agent = user_agent.map(|s| CString::new(s).unwrap());
+        let user_css = user_css.map(|s| CString::new(s).unwrap());
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamHTMLSurface_CreateBrowser(
+                self.html,
+                user_agent.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+                user_css.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            );
+            register_call_result::<sys::HTML_BrowserReady_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 1,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else {
+                        Ok(BrowserHandle(v.unBrowserHandle))
+                    })
+                },
+            );
+        }
+    }
+
+    /// Closes the browser and frees its resources
+    pub fn remove_browser(&self, browser: BrowserHandle) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_RemoveBrowser(self.html, browser.0);
+        }
+    }
+
+    /// Navigates the browser to the given url, sending the post data if it
+    /// is given
+    pub fn load_url(&self, browser: BrowserHandle, url: &str, post_data: Option<&str>) {
+        let url = CString::new(url).unwrap();
+        let post_data = post_data.map(|s| CString::new(s).unwrap());
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_LoadURL(
+                self.html,
+                browser.0,
+                url.as_ptr(),
+                post_data.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            );
+        }
+    }
+
+    /// Stops loading the current page
+    pub fn stop_load(&self, browser: BrowserHandle) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_StopLoad(self.html, browser.0);
+        }
+    }
+
+    /// Reloads the current page
+    pub fn reload(&self, browser: BrowserHandle) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_Reload(self.html, browser.0);
+        }
+    }
+
+    /// Navigates to the previous page in the history
+    pub fn go_back(&self, browser: BrowserHandle) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_GoBack(self.html, browser.0);
+        }
+    }
+
+    /// Navigates to the next page in the history
+    pub fn go_forward(&self, browser: BrowserHandle) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_GoForward(self.html, browser.0);
+        }
+    }
+
+    /// Allows or blocks the navigation reported by the `HtmlStartRequest`
+    /// callback.
+    ///
+    /// This must be called for every `HtmlStartRequest`, the browser doesn't
+    /// continue until it is.
+    pub fn allow_start_request(&self, browser: BrowserHandle, allowed: bool) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_AllowStartRequest(self.html, browser.0, allowed);
+        }
+    }
+
+    /// Sets the size of the page in pixels, e.g. to match the texture it is
+    /// drawn to
+    pub fn set_size(&self, browser: BrowserHandle, width: u32, height: u32) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_SetSize(self.html, browser.0, width, height);
+        }
+    }
+
+    /// Sets the DPI scale of the browser, e.g. `2.0` on high DPI displays.
+    ///
+    /// This should be called before loading any pages.
+    pub fn set_dpi_scaling_factor(&self, browser: BrowserHandle, scale: f32) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_SetDPIScalingFactor(self.html, browser.0, scale);
+        }
+    }
+
+    /// Zooms the page around the given point, a `zoom` of `1.0` is the
+    /// default size
+    pub fn set_page_scale_factor(&self, browser: BrowserHandle, zoom: f32, x: i32, y: i32) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_SetPageScaleFactor(self.html, browser.0, zoom, x, y);
+        }
+    }
+
+    /// Searches the page for the given text, the results are returned
+    /// through the `HtmlSearchResults` callback.
+    ///
+    /// `currently_in_find` should be set when searching for the same text
+    /// again to move to the next match.
+    pub fn find(&self, browser: BrowserHandle, text: &str, currently_in_find: bool, reverse: bool) {
+        let text = CString::new(text).unwrap();
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_Find(
+                self.html,
+                browser.0,
+                text.as_ptr(),
+                currently_in_find,
+                reverse,
+            );
+        }
+    }
+
+    /// Stops the search started with `find` and clears the highlighting
+    pub fn stop_find(&self, browser: BrowserHandle) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_StopFind(self.html, browser.0);
+        }
+    }
+
+    /// Sets a cookie for every browser, e.g. to show pages as a logged in
+    /// user
+    pub fn set_cookie(&self, cookie: &HtmlCookie) {
+        let hostname = CString::new(cookie.hostname).unwrap();
+        let key = CString::new(cookie.key).unwrap();
+        let value = CString::new(cookie.value).unwrap();
+        let path = CString::new(cookie.path).unwrap();
+        let expires = cookie.expires.map_or(0, |expires| {
+            expires
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() as u32)
+        });
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_SetCookie(
+                self.html,
+                hostname.as_ptr(),
+                key.as_ptr(),
+                value.as_ptr(),
+                path.as_ptr(),
+                expires,
+                cookie.secure,
+                cookie.http_only,
+            );
+        }
+    }
+
+    /// Sets whether the browser is in the background, which throttles
+    /// rendering and javascript while it isn't visible
+    pub fn set_background_mode(&self, browser: BrowserHandle, background: bool) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_SetBackgroundMode(self.html, browser.0, background);
+        }
+    }
+
+    /// Requests the link at the given position on the page, the result is
+    /// returned through the `HtmlLinkAtPosition` callback.
+    pub fn get_link_at_position(&self, browser: BrowserHandle, x: i32, y: i32) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_GetLinkAtPosition(self.html, browser.0, x, y);
+        }
+    }
+
+    /// Responds to the `HtmlJsAlert` or `HtmlJsConfirm` dialog of the
+    /// browser, `result` is whether the user accepted it.
+    ///
+    /// The page is blocked until this is called.
+    pub fn js_dialog_response(&self, browser: BrowserHandle, result: bool) {
+        unsafe {
+            sys::SteamAPI_ISteamHTMLSurface_JSDialogResponse(self.html, browser.0, result);
+        }
+    }
+
+    /// Responds to the `HtmlFileOpenDialog` of the browser with the files the
+    /// user selected, or `None` if they cancelled the dialog.
+    ///
+    /// The page is blocked until this is called.
+    pub fn file_load_dialog_response<P: AsRef<Path>>(
+        &self,
+        browser: BrowserHandle,
+        files: Option<&[P]>,
+    ) {
+        let files: Option<Vec<CString>> = files.map(|files| {
+            files
+                .iter()
+                .map(|f| CString::new(f.as_ref().to_string_lossy().into_owned()).unwrap())
+                .collect()
+        });
+        unsafe {
+            match files {
+                Some(files) => {
+                    // Steam expects a null terminated array of paths
+                    let mut ptrs: Vec<*const std::os::raw::c_char> =
+                        files.iter().map(|f| f.as_ptr()).collect();
+                    ptrs.push(std::ptr::null());
+                    sys::SteamAPI_ISteamHTMLSurface_FileLoadDialogResponse(
+                        self.html,
+                        browser.0,
+                        ptrs.as_mut_ptr(),
+                    );
+                }
+                None => sys::SteamAPI_ISteamHTMLSurface_FileLoadDialogResponse(
+                    self.html,
+                    browser.0,
+                    std::ptr::null_mut(),
+                ),
+            }
+        }
+    }
+}
+
+/// A cookie set with `HtmlSurface::set_cookie`
+#[derive(Clone, Debug)]
+pub struct HtmlCookie<'a> {
+    /// The host the cookie is sent to, e.g. `example.com`
+    pub hostname: &'a str,
+    /// The name of the cookie
+    pub key: &'a str,
+    /// The value of the cookie
+    pub value: &'a str,
+    /// The path the cookie is sent for, e.g. `/`
+    pub path: &'a str,
+    /// When the cookie expires, `None` for a session cookie
+    pub expires: Option<SystemTime>,
+    /// Whether the cookie is only sent over https
+    pub secure: bool,
+    /// Whether the cookie is hidden from javascript
+    pub http_only: bool,
+}
+
+/// Copies a string from a callback, treating null as empty
+unsafe fn callback_string(s: *const std::os::raw::c_char) -> String {
+    if s.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(s).to_string_lossy().into_owned()
+    }
+}
+
+/// Called when a browser needs to be repainted
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlNeedsPaint {
+    /// The browser that needs to be repainted
+    pub browser: BrowserHandle,
+    /// The full page in BGRA format, `width * height * 4` bytes
+    pub bgra: Vec<u8>,
+    /// The width of the page in pixels
+    pub width: u32,
+    /// The height of the page in pixels
+    pub height: u32,
+    /// The left edge of the area that changed since the last paint
+    pub update_x: u32,
+    /// The top edge of the area that changed since the last paint
+    pub update_y: u32,
+    /// The width of the area that changed since the last paint
+    pub update_width: u32,
+    /// The height of the area that changed since the last paint
+    pub update_height: u32,
+    /// The horizontal scroll position of the page
+    pub scroll_x: u32,
+    /// The vertical scroll position of the page
+    pub scroll_y: u32,
+    /// The scale factor of the page
+    pub page_scale: f32,
+    /// Incremented whenever a new page is loaded
+    pub page_serial: u32,
+}
+
+unsafe impl Callback for HtmlNeedsPaint {
+    const ID: i32 = CALLBACK_BASE_ID + 2;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_NeedsPaint_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_NeedsPaint_t);
+        // The buffer is only valid during the callback so it has to be copied
+        let bgra = if val.pBGRA.is_null() {
+            Vec::new()
+        } else {
+            let len = val.unWide as usize * val.unTall as usize * 4;
+            std::slice::from_raw_parts(val.pBGRA as *const u8, len).to_vec()
+        };
+        HtmlNeedsPaint {
+            browser: BrowserHandle(val.unBrowserHandle),
+            bgra,
+            width: val.unWide,
+            height: val.unTall,
+            update_x: val.unUpdateX,
+            update_y: val.unUpdateY,
+            update_width: val.unUpdateWide,
+            update_height: val.unUpdateTall,
+            scroll_x: val.unScrollX,
+            scroll_y: val.unScrollY,
+            page_scale: val.flPageScale,
+            page_serial: val.unPageSerial,
+        }
+    }
+}
+
+/// Called when a browser is about to navigate to a new page.
+///
+/// The navigation must be allowed or blocked with
+/// `HtmlSurface::allow_start_request`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlStartRequest {
+    /// The browser that is navigating
+    pub browser: BrowserHandle,
+    /// The url that is being loaded
+    pub url: String,
+    /// The target frame of the navigation, e.g. `_blank`
+    pub target: String,
+    /// The post data sent with the request, if any
+    pub post_data: String,
+    /// Whether the request is a redirect
+    pub is_redirect: bool,
+}
+
+unsafe impl Callback for HtmlStartRequest {
+    const ID: i32 = CALLBACK_BASE_ID + 3;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_StartRequest_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_StartRequest_t);
+        HtmlStartRequest {
+            browser: BrowserHandle(val.unBrowserHandle),
+            url: callback_string(val.pchURL),
+            target: callback_string(val.pchTarget),
+            post_data: callback_string(val.pchPostData),
+            is_redirect: val.bIsRedirect,
+        }
+    }
+}
+
+/// Called when the url of a browser changed
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlUrlChanged {
+    /// The browser whose url changed
+    pub browser: BrowserHandle,
+    /// The new url
+    pub url: String,
+    /// The post data sent with the request, if any
+    pub post_data: String,
+    /// Whether the change was caused by a redirect
+    pub is_redirect: bool,
+    /// The title of the page
+    pub page_title: String,
+    /// Whether this is a new navigation rather than a change within the page
+    pub new_navigation: bool,
+}
+
+unsafe impl Callback for HtmlUrlChanged {
+    const ID: i32 = CALLBACK_BASE_ID + 5;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_URLChanged_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_URLChanged_t);
+        HtmlUrlChanged {
+            browser: BrowserHandle(val.unBrowserHandle),
+            url: callback_string(val.pchURL),
+            post_data: callback_string(val.pchPostData),
+            is_redirect: val.bIsRedirect,
+            page_title: callback_string(val.pchPageTitle),
+            new_navigation: val.bNewNavigation,
+        }
+    }
+}
+
+/// Called when a browser finished loading a page
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlFinishedRequest {
+    /// The browser that loaded the page
+    pub browser: BrowserHandle,
+    /// The url of the page
+    pub url: String,
+    /// The title of the page
+    pub page_title: String,
+}
+
+unsafe impl Callback for HtmlFinishedRequest {
+    const ID: i32 = CALLBACK_BASE_ID + 6;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_FinishedRequest_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_FinishedRequest_t);
+        HtmlFinishedRequest {
+            browser: BrowserHandle(val.unBrowserHandle),
+            url: callback_string(val.pchURL),
+            page_title: callback_string(val.pchPageTitle),
+        }
+    }
+}
+
+/// Called with the result of `HtmlSurface::get_link_at_position`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlLinkAtPosition {
+    /// The browser the link was requested from
+    pub browser: BrowserHandle,
+    /// The horizontal position that was requested
+    pub x: u32,
+    /// The vertical position that was requested
+    pub y: u32,
+    /// The url of the link, empty if there is no link at the position
+    pub url: String,
+    /// Whether the position is an input field
+    pub is_input: bool,
+    /// Whether the link is live
+    pub is_live_link: bool,
+}
+
+unsafe impl Callback for HtmlLinkAtPosition {
+    const ID: i32 = CALLBACK_BASE_ID + 13;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_LinkAtPosition_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_LinkAtPosition_t);
+        HtmlLinkAtPosition {
+            browser: BrowserHandle(val.unBrowserHandle),
+            x: val.x,
+            y: val.y,
+            url: callback_string(val.pchURL),
+            is_input: val.bInput,
+            is_live_link: val.bLiveLink,
+        }
+    }
+}
+
+/// Called when a page shows a javascript alert.
+///
+/// The page is blocked until `HtmlSurface::js_dialog_response` is called.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlJsAlert {
+    /// The browser showing the alert
+    pub browser: BrowserHandle,
+    /// The message of the alert
+    pub message: String,
+}
+
+unsafe impl Callback for HtmlJsAlert {
+    const ID: i32 = CALLBACK_BASE_ID + 14;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_JSAlert_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_JSAlert_t);
+        HtmlJsAlert {
+            browser: BrowserHandle(val.unBrowserHandle),
+            message: callback_string(val.pchMessage),
+        }
+    }
+}
+
+/// Called when a page shows a javascript confirmation dialog.
+///
+/// The page is blocked until `HtmlSurface::js_dialog_response` is called.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlJsConfirm {
+    /// The browser showing the dialog
+    pub browser: BrowserHandle,
+    /// The message of the dialog
+    pub message: String,
+}
+
+unsafe impl Callback for HtmlJsConfirm {
+    const ID: i32 = CALLBACK_BASE_ID + 15;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_JSConfirm_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_JSConfirm_t);
+        HtmlJsConfirm {
+            browser: BrowserHandle(val.unBrowserHandle),
+            message: callback_string(val.pchMessage),
+        }
+    }
+}
+
+/// Called when a page wants the user to pick files to upload.
+///
+/// The page is blocked until `HtmlSurface::file_load_dialog_response` is
+/// called.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlFileOpenDialog {
+    /// The browser that opened the dialog
+    pub browser: BrowserHandle,
+    /// The title of the dialog
+    pub title: String,
+    /// The file that should be selected initially
+    pub initial_file: String,
+}
+
+unsafe impl Callback for HtmlFileOpenDialog {
+    const ID: i32 = CALLBACK_BASE_ID + 16;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_FileOpenDialog_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_FileOpenDialog_t);
+        HtmlFileOpenDialog {
+            browser: BrowserHandle(val.unBrowserHandle),
+            title: callback_string(val.pchTitle),
+            initial_file: callback_string(val.pchInitialFile),
+        }
+    }
+}
+
+/// Called when a page tries to open a popup window.
+///
+/// Steam doesn't create a browser for the window, one can be created with
+/// `HtmlSurface::create_browser` and navigated to the url if popups
+/// should be supported.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlNewWindow {
+    /// The browser that opened the window
+    pub browser: BrowserHandle,
+    /// The url the window wants to load
+    pub url: String,
+    /// The requested left edge of the window
+    pub x: u32,
+    /// The requested top edge of the window
+    pub y: u32,
+    /// The requested width of the window
+    pub width: u32,
+    /// The requested height of the window
+    pub height: u32,
+}
+
+unsafe impl Callback for HtmlNewWindow {
+    const ID: i32 = CALLBACK_BASE_ID + 21;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_NewWindow_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_NewWindow_t);
+        HtmlNewWindow {
+            browser: BrowserHandle(val.unBrowserHandle),
+            url: callback_string(val.pchURL),
+            x: val.unX,
+            y: val.unY,
+            width: val.unWide,
+            height: val.unTall,
+        }
+    }
+}
+
+/// Called with the results of `HtmlSurface::find`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlSearchResults {
+    /// The browser that was searched
+    pub browser: BrowserHandle,
+    /// The number of matches on the page
+    pub results: u32,
+    /// The index of the highlighted match
+    pub current_match: u32,
+}
+
+unsafe impl Callback for HtmlSearchResults {
+    const ID: i32 = CALLBACK_BASE_ID + 9;
+    const SIZE: i32 = ::std::mem::size_of::<sys::HTML_SearchResults_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::HTML_SearchResults_t);
+        HtmlSearchResults {
+            browser: BrowserHandle(val.unBrowserHandle),
+            results: val.unResults,
+            current_match: val.unCurrentMatch,
+        }
+    }
+}
@@ -0,0 +1,532 @@
+use super::*;
+
+use std::time::Duration;
+
+#[cfg(test)]
+use serial_test_derive::serial;
+
+const CALLBACK_BASE_ID: i32 = 2100;
+
+/// Access to the steam HTTP interface
+///
+/// Requests are sent through the steam client and use its proxy settings.
+pub struct Http<Manager> {
+    pub(crate) http: *mut sys::ISteamHTTP,
+    pub(crate) inner: Arc<Inner<Manager>>,
+}
+
+impl<Manager> Http<Manager> {
+    /// Creates a new request for the given absolute url, e.g.
+    /// `https://example.com/scores`.
+    ///
+    /// The request isn't sent until `HttpRequest::send` is called.
+    ///
+    /// Fails with `SteamError::InvalidParameter` if steam rejects the url,
+    /// e.g. because it isn't absolute or contains a nul byte.
+    pub fn new_request(
+        &self,
+        method: HttpMethod,
+        url: &str,
+    ) -> Result<HttpRequest<Manager>, SteamError> {
+        let url = CString::new(url).map_err(|_| SteamError::InvalidParameter)?;
+        unsafe {
+            let handle =
+                sys::SteamAPI_ISteamHTTP_CreateHTTPRequest(self.http, method.into(), url.as_ptr());
+            if handle == sys::INVALID_HTTPREQUEST_HANDLE {
+                return Err(SteamError::InvalidParameter);
+            }
+            Ok(HttpRequest {
+                http: self.http,
+                inner: self.inner.clone(),
+                handle,
+                method,
+                error: None,
+            })
+        }
+    }
+
+    /// Creates a GET request for the given absolute url.
+    ///
+    /// With the `futures` feature the request can be awaited directly,
+    /// see `HttpRequest::send_future`.
+    pub fn get(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Get, url)
+    }
+
+    /// Creates a HEAD request for the given absolute url
+    pub fn head(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Head, url)
+    }
+
+    /// Creates a POST request for the given absolute url
+    pub fn post(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Post, url)
+    }
+
+    /// Creates a PUT request for the given absolute url
+    pub fn put(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Put, url)
+    }
+
+    /// Creates a DELETE request for the given absolute url
+    pub fn delete(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Delete, url)
+    }
+
+    /// Creates an OPTIONS request for the given absolute url
+    pub fn options(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Options, url)
+    }
+
+    /// Creates a PATCH request for the given absolute url
+    pub fn patch(&self, url: &str) -> Result<HttpRequest<Manager>, SteamError> {
+        self.new_request(HttpMethod::Patch, url)
+    }
+}
+
+/// The method of an HTTP request
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HttpMethod {
+    Get,
+    Head,
+    Post,
+    Put,
+    Delete,
+    Options,
+    Patch,
+}
+
+impl From<HttpMethod> for sys::EHTTPMethod {
+    fn from(method: HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => sys::EHTTPMethod::k_EHTTPMethodGET,
+            HttpMethod::Head => sys::EHTTPMethod::k_EHTTPMethodHEAD,
+            HttpMethod::Post => sys::EHTTPMethod::k_EHTTPMethodPOST,
+            HttpMethod::Put => sys::EHTTPMethod::k_EHTTPMethodPUT,
+            HttpMethod::Delete => sys::EHTTPMethod::k_EHTTPMethodDELETE,
+            HttpMethod::Options => sys::EHTTPMethod::k_EHTTPMethodOPTIONS,
+            HttpMethod::Patch => sys::EHTTPMethod::k_EHTTPMethodPATCH,
+        }
+    }
+}
+
+/// An HTTP request that hasn't been sent yet, see `Http::new_request`
+///
+/// The request is released if it is dropped without being sent. If steam rejects
+/// one of the builder calls, `send` fails with the first error instead of sending
+/// an incomplete request.
+pub struct HttpRequest<Manager> {
+    http: *mut sys::ISteamHTTP,
+    inner: Arc<Inner<Manager>>,
+    handle: sys::HTTPRequestHandle,
+    method: HttpMethod,
+    error: Option<SteamError>,
+}
+
+impl<Manager> HttpRequest<Manager> {
+    /// Records a failed builder call, keeping the first error
+    fn check(mut self, ok: bool) -> Self {
+        if !ok && self.error.is_none() {
+            self.error = Some(SteamError::InvalidParameter);
+        }
+        self
+    }
+
+    /// Sets a header of the request
+    pub fn header(self, name: &str, value: &str) -> Self {
+        let (name, value) = match (CString::new(name), CString::new(value)) {
+            (Ok(name), Ok(value)) => (name, value),
+            _ => return self.check(false),
+        };
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestHeaderValue(
+                self.http,
+                self.handle,
+                name.as_ptr(),
+                value.as_ptr(),
+            )
+        };
+        self.check(ok)
+    }
+
+    /// Adds a parameter to the request.
+    ///
+    /// Parameters are url encoded by steam and sent in the query string of GET
+    /// requests and in the form encoded body of POST requests. Steam doesn't send
+    /// them for any other method, so for those `send` fails with
+    /// `SteamError::InvalidParameter`, add them to the url instead.
+    pub fn parameter(self, name: &str, value: &str) -> Self {
+        if !matches!(self.method, HttpMethod::Get | HttpMethod::Post) {
+            return self.check(false);
+        }
+        let (name, value) = match (CString::new(name), CString::new(value)) {
+            (Ok(name), Ok(value)) => (name, value),
+            _ => return self.check(false),
+        };
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestGetOrPostParameter(
+                self.http,
+                self.handle,
+                name.as_ptr(),
+                value.as_ptr(),
+            )
+        };
+        self.check(ok)
+    }
+
+    /// Adds several parameters to the request, see `parameter`
+    pub fn parameters<K, V>(mut self, parameters: &[(K, V)]) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (name, value) in parameters {
+            self = self.parameter(name.as_ref(), value.as_ref());
+        }
+        self
+    }
+
+    /// Sets the body of the request to the url encoded parameters, see `raw_body`.
+    ///
+    /// Unlike `parameter` this can be used for PUT requests.
+    pub fn form_body<K, V>(self, parameters: &[(K, V)]) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let body = encode_form(parameters);
+        self.raw_body("application/x-www-form-urlencoded", body.as_bytes())
+    }
+
+    /// Sets the body of the request.
+    ///
+    /// Steam only accepts a body for POST and PUT requests that don't have any
+    /// parameters added with `parameter`, otherwise `send` fails with
+    /// `SteamError::InvalidParameter`.
+    pub fn raw_body(self, content_type: &str, body: &[u8]) -> Self {
+        let content_type = match CString::new(content_type) {
+            Ok(content_type) => content_type,
+            Err(_) => return self.check(false),
+        };
+        let len = match u32::try_from(body.len()) {
+            Ok(len) => len,
+            Err(_) => return self.check(false),
+        };
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestRawPostBody(
+                self.http,
+                self.handle,
+                content_type.as_ptr(),
+                body.as_ptr() as *mut _,
+                len,
+            )
+        };
+        self.check(ok)
+    }
+
+    /// Sets a value that is returned with the response, e.g. to match
+    /// responses to requests in logs
+    pub fn context_value(self, value: u64) -> Self {
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestContextValue(self.http, self.handle, value)
+        };
+        self.check(ok)
+    }
+
+    /// Sets how long the request may go without any network activity before
+    /// it times out. The default is 60 seconds.
+    ///
+    /// Steam only supports whole seconds, so the timeout is rounded up.
+    pub fn network_activity_timeout(self, timeout: Duration) -> Self {
+        let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestNetworkActivityTimeout(
+                self.http,
+                self.handle,
+                secs.min(u32::MAX as u64) as u32,
+            )
+        };
+        self.check(ok)
+    }
+
+    /// Sets how long the request may take in total before it times out,
+    /// regardless of network activity
+    pub fn absolute_timeout(self, timeout: Duration) -> Self {
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestAbsoluteTimeoutMS(
+                self.http,
+                self.handle,
+                timeout.as_millis().min(u32::MAX as u128) as u32,
+            )
+        };
+        self.check(ok)
+    }
+
+    /// Sets whether the server must present a valid certificate for https
+    /// requests
+    pub fn requires_verified_certificate(self, required: bool) -> Self {
+        let ok = unsafe {
+            sys::SteamAPI_ISteamHTTP_SetHTTPRequestRequiresVerifiedCertificate(
+                self.http,
+                self.handle,
+                required,
+            )
+        };
+        self.check(ok)
+    }
+
+    /// Sends the request, calling the callback once the response arrived.
+    ///
+    /// Responses with error status codes are still returned as `Ok`, only
+    /// requests that didn't get a response at all fail, with
+    /// `SteamError::Timeout` if they timed out. Requests with a rejected
+    /// builder call fail without being sent.
+    pub fn send<F>(mut self, cb: F)
+    where
+        F: FnOnce(Result<HttpResponse<Manager>, SteamError>) + 'static + Send,
+        Manager: Send + Sync + 'static,
+    {
+        if let Some(error) = self.error.take() {
+            // Dropping the request releases it
+            cb(Err(error));
+            return;
+        }
+        let handle = std::mem::replace(&mut self.handle, sys::INVALID_HTTPREQUEST_HANDLE);
+        unsafe {
+            let mut api_call = 0;
+            if !sys::SteamAPI_ISteamHTTP_SendHTTPRequest(self.http, handle, &mut api_call) {
+                sys::SteamAPI_ISteamHTTP_ReleaseHTTPRequest(self.http, handle);
+                cb(Err(SteamError::InvalidParameter));
+                return;
+            }
+            // Sending the pointer to the interface is fine, it lives as long as steam
+            let http = self.http as usize;
+            let inner = Arc::downgrade(&self.inner);
+            register_call_result::<sys::HTTPRequestCompleted_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 1,
+                move |v, io_error| {
+                    let http = http as *mut sys::ISteamHTTP;
+                    let inner = match inner.upgrade() {
+                        Some(inner) => inner,
+                        None => return,
+                    };
+                    if io_error || !v.m_bRequestSuccessful {
+                        let mut timed_out = false;
+                        sys::SteamAPI_ISteamHTTP_GetHTTPRequestWasTimedOut(
+                            http,
+                            v.m_hRequest,
+                            &mut timed_out,
+                        );
+                        sys::SteamAPI_ISteamHTTP_ReleaseHTTPRequest(http, v.m_hRequest);
+                        cb(Err(if timed_out {
+                            SteamError::Timeout
+                        } else {
+                            SteamError::IOFailure
+                        }));
+                    } else {
+                        // Status codes steam doesn't know about aren't valid
+                        // `EHTTPStatusCode`s, so the field is read as a plain integer
+                        let status = std::ptr::read_unaligned(
+                            std::ptr::addr_of!(v.m_eStatusCode) as *const u32
+                        ) as u16;
+                        cb(Ok(HttpResponse {
+                            http,
+                            _inner: inner,
+                            handle: v.m_hRequest,
+                            status,
+                            body_size: v.m_unBodySize,
+                            context_value: v.m_ulContextValue,
+                        }));
+                    }
+                },
+            );
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<Manager: Send + Sync + 'static> HttpRequest<Manager> {
+    /// Like `send`, but returns a future instead of taking a callback.
+    ///
+    /// Awaiting a request calls this as well:
+    ///
+    /// ```no_run
+    /// # async fn example(client: steamworks::Client) -> Result<(), steamworks::SteamError> {
+    /// let response = client.http().get("https://example.com/news")?.await?;
+    /// println!("{}: {:?}", response.status(), response.header("Content-Type"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_future(self) -> CallResultFuture<Result<HttpResponse<Manager>, SteamError>> {
+        let (future, complete) = CallResultFuture::new();
+        self.send(complete);
+        future
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<Manager: Send + Sync + 'static> std::future::IntoFuture for HttpRequest<Manager> {
+    type Output = Result<HttpResponse<Manager>, SteamError>;
+    type IntoFuture = CallResultFuture<Self::Output>;
+
+    /// Sends the request, see `send_future`
+    fn into_future(self) -> Self::IntoFuture {
+        self.send_future()
+    }
+}
+
+impl<Manager> Drop for HttpRequest<Manager> {
+    fn drop(&mut self) {
+        if self.handle != sys::INVALID_HTTPREQUEST_HANDLE {
+            unsafe {
+                sys::SteamAPI_ISteamHTTP_ReleaseHTTPRequest(self.http, self.handle);
+            }
+        }
+    }
+}
+
+/// The response to an HTTP request, see `HttpRequest::send`
+///
+/// The response is released when dropped.
+pub struct HttpResponse<Manager> {
+    http: *mut sys::ISteamHTTP,
+    _inner: Arc<Inner<Manager>>,
+    handle: sys::HTTPRequestHandle,
+    status: u16,
+    body_size: u32,
+    context_value: u64,
+}
+
+unsafe impl<Manager: Send + Sync> Send for HttpResponse<Manager> {}
+
+impl<Manager> HttpResponse<Manager> {
+    /// Returns the status code of the response, e.g. 200
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the value set with `HttpRequest::context_value`, 0 by default
+    pub fn context_value(&self) -> u64 {
+        self.context_value
+    }
+
+    /// Returns the value of the given response header, if it was sent
+    pub fn header(&self, name: &str) -> Option<String> {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            let mut size = 0;
+            if !sys::SteamAPI_ISteamHTTP_GetHTTPResponseHeaderSize(
+                self.http,
+                self.handle,
+                name.as_ptr(),
+                &mut size,
+            ) {
+                return None;
+            }
+            let mut value = vec![0; size as usize];
+            if !sys::SteamAPI_ISteamHTTP_GetHTTPResponseHeaderValue(
+                self.http,
+                self.handle,
+                name.as_ptr(),
+                value.as_mut_ptr(),
+                size,
+            ) {
+                return None;
+            }
+            // The value may include the nul terminator
+            if value.last() == Some(&0) {
+                value.pop();
+            }
+            Some(String::from_utf8_lossy(&value).into_owned())
+        }
+    }
+
+    /// Returns the body of the response
+    pub fn body(&self) -> Vec<u8> {
+        unsafe {
+            let mut body = vec![0; self.body_size as usize];
+            if sys::SteamAPI_ISteamHTTP_GetHTTPResponseBodyData(
+                self.http,
+                self.handle,
+                body.as_mut_ptr(),
+                self.body_size,
+            ) {
+                body
+            } else {
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl<Manager> Drop for HttpResponse<Manager> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::SteamAPI_ISteamHTTP_ReleaseHTTPRequest(self.http, self.handle);
+        }
+    }
+}
+
+/// Encodes the parameters as `application/x-www-form-urlencoded`
+fn encode_form<K, V>(parameters: &[(K, V)]) -> String
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut form = String::new();
+    for (name, value) in parameters {
+        if !form.is_empty() {
+            form.push('&');
+        }
+        url_encode_into(&mut form, name.as_ref());
+        form.push('=');
+        url_encode_into(&mut form, value.as_ref());
+    }
+    form
+}
+
+fn url_encode_into(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+}
+
+#[test]
+fn test_encode_form() {
+    assert_eq!(encode_form::<&str, &str>(&[]), "");
+    assert_eq!(
+        encode_form(&[("name", "Jane Doe"), ("score", "100")]),
+        "name=Jane+Doe&score=100"
+    );
+    assert_eq!(
+        encode_form(&[("q", "a&b=c/d"), ("emoji", "\u{1F600}")]),
+        "q=a%26b%3Dc%2Fd&emoji=%F0%9F%98%80"
+    );
+}
+
+#[test]
+#[serial]
+fn test_parameter_unsupported_method() {
+    let (client, _single) = Client::init().unwrap();
+    let result = Arc::new(Mutex::new(None));
+    let result2 = result.clone();
+    client
+        .http()
+        .delete("https://example.com/scores")
+        .unwrap()
+        .parameter("name", "Jane Doe")
+        .send(move |res| *result2.lock().unwrap() = Some(res.map(|_| ())));
+    assert_eq!(
+        *result.lock().unwrap(),
+        Some(Err(SteamError::InvalidParameter))
+    );
+}
@@ -2,6 +2,11 @@ use sys::InputHandle_t;
 
 use super::*;
 
+use std::os::raw::c_char;
+use std::path::PathBuf;
+
+const CALLBACK_BASE_ID: i32 = 2800;
+
 /// Access to the steam input interface
 pub struct Input<Manager> {
     pub(crate) input: *mut sys::ISteamInput,
@@ -12,9 +17,24 @@ impl<Manager> Input<Manager> {
     /// Init must be called when starting use of this interface.
     /// if explicitly_call_run_frame is called then you will need to manually call RunFrame
     /// each frame, otherwise Steam Input will updated when SteamAPI_RunCallbacks() is called
-    pub fn init(&self, explicitly_call_run_frame: bool) {
+    ///
+    /// Returns whether steam input was initialized.
+    pub fn init(&self, explicitly_call_run_frame: bool) -> bool {
+        unsafe { sys::SteamAPI_ISteamInput_Init(self.input, explicitly_call_run_frame) }
+    }
+
+    /// Sets the path of the input action manifest to use instead of the
+    /// configuration from the steamworks partner site, e.g. during development.
+    ///
+    /// Must be called after `init`. Returns whether the file was loaded, which
+    /// fails if the path contains a nul byte.
+    pub fn set_input_action_manifest_file_path(&self, path: &str) -> bool {
+        let path = match CString::new(path) {
+            Ok(path) => path,
+            Err(_) => return false,
+        };
         unsafe {
-            sys::SteamAPI_ISteamInput_Init(self.input, explicitly_call_run_frame);
+            sys::SteamAPI_ISteamInput_SetInputActionManifestFilePath(self.input, path.as_ptr())
         }
     }
 
@@ -23,6 +43,9 @@ impl<Manager> Input<Manager> {
     /// possible latency, you call this directly before reading controller state.
     /// Note: This must be called from somewhere before GetConnectedControllers will
     /// return any handles
+    ///
+    /// Unless `init` was called with `explicitly_call_run_frame` set,
+    /// `SingleClient::run_callbacks` does this as well.
     pub fn run_frame(&self) {
         unsafe { sys::SteamAPI_ISteamInput_RunFrame(self.input, false) }
     }
@@ -30,16 +53,23 @@ impl<Manager> Input<Manager> {
     /// Returns a list of the currently connected controllers
     pub fn get_connected_controllers(&self) -> Vec<sys::InputHandle_t> {
         unsafe {
-            let handles = [0_u64; sys::STEAM_INPUT_MAX_COUNT as usize].as_mut_ptr();
-            let quantity = sys::SteamAPI_ISteamInput_GetConnectedControllers(self.input, handles);
-            if quantity == 0 {
-                Vec::new()
-            } else {
-                std::slice::from_raw_parts(handles as *const _, quantity as usize).to_vec()
-            }
+            let mut handles = vec![0_u64; sys::STEAM_INPUT_MAX_COUNT as usize];
+            let quantity =
+                sys::SteamAPI_ISteamInput_GetConnectedControllers(self.input, handles.as_mut_ptr());
+            handles.truncate(quantity as usize);
+            handles
         }
     }
 
+    /// Enables the `SteamInputDeviceConnected` and `SteamInputDeviceDisconnected`
+    /// callbacks.
+    ///
+    /// Once enabled a `SteamInputDeviceConnected` callback is sent for every
+    /// controller that is already connected.
+    pub fn enable_device_callbacks(&self) {
+        unsafe { sys::SteamAPI_ISteamInput_EnableDeviceCallbacks(self.input) }
+    }
+
     /// Returns a list of the currently connected controllers without allocating, and the count
     pub fn get_connected_controllers_slice(
         &self,
@@ -73,6 +103,70 @@ impl<Manager> Input<Manager> {
         }
     }
 
+    /// Returns the action set the controller is currently using
+    pub fn get_current_action_set(
+        &self,
+        input_handle: sys::InputHandle_t,
+    ) -> sys::InputActionSetHandle_t {
+        unsafe { sys::SteamAPI_ISteamInput_GetCurrentActionSet(self.input, input_handle) }
+    }
+
+    /// Activates an action set layer on top of the controller's current action set.
+    ///
+    /// Action set layers are looked up with `get_action_set_handle`, like action
+    /// sets. Layers activated later take priority over earlier ones.
+    pub fn activate_action_set_layer(
+        &self,
+        input_handle: sys::InputHandle_t,
+        action_set_layer_handle: sys::InputActionSetHandle_t,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_ActivateActionSetLayer(
+                self.input,
+                input_handle,
+                action_set_layer_handle,
+            )
+        }
+    }
+
+    /// Deactivates an action set layer activated with `activate_action_set_layer`
+    pub fn deactivate_action_set_layer(
+        &self,
+        input_handle: sys::InputHandle_t,
+        action_set_layer_handle: sys::InputActionSetHandle_t,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_DeactivateActionSetLayer(
+                self.input,
+                input_handle,
+                action_set_layer_handle,
+            )
+        }
+    }
+
+    /// Deactivates every action set layer of the controller
+    pub fn deactivate_all_action_set_layers(&self, input_handle: sys::InputHandle_t) {
+        unsafe { sys::SteamAPI_ISteamInput_DeactivateAllActionSetLayers(self.input, input_handle) }
+    }
+
+    /// Returns the currently active action set layers of the controller, in
+    /// the order they were activated
+    pub fn get_active_action_set_layers(
+        &self,
+        input_handle: sys::InputHandle_t,
+    ) -> Vec<sys::InputActionSetHandle_t> {
+        unsafe {
+            let mut handles = vec![0_u64; sys::STEAM_INPUT_MAX_ACTIVE_LAYERS as usize];
+            let quantity = sys::SteamAPI_ISteamInput_GetActiveActionSetLayers(
+                self.input,
+                input_handle,
+                handles.as_mut_ptr(),
+            );
+            handles.truncate(quantity as usize);
+            handles
+        }
+    }
+
     /// Get the handle of the specified Digital action.
     pub fn get_digital_action_handle(&self, action_name: &str) -> sys::InputDigitalActionHandle_t {
         let name = CString::new(action_name).unwrap();
@@ -90,9 +184,42 @@ impl<Manager> Input<Manager> {
         &self,
         input_handle: sys::InputHandle_t,
         action_handle: sys::InputDigitalActionHandle_t,
-    ) -> sys::InputDigitalActionData_t {
+    ) -> DigitalActionData {
         unsafe {
-            sys::SteamAPI_ISteamInput_GetDigitalActionData(self.input, input_handle, action_handle)
+            let data = sys::SteamAPI_ISteamInput_GetDigitalActionData(
+                self.input,
+                input_handle,
+                action_handle,
+            );
+            DigitalActionData {
+                state: data.bState,
+                active: data.bActive,
+            }
+        }
+    }
+
+    /// Returns the buttons etc. the digital action is bound to in the given
+    /// action set of the controller.
+    pub fn get_digital_action_origins(
+        &self,
+        input_handle: sys::InputHandle_t,
+        action_set_handle: sys::InputActionSetHandle_t,
+        action_handle: sys::InputDigitalActionHandle_t,
+    ) -> Vec<InputActionOrigin> {
+        unsafe {
+            let mut origins = vec![
+                sys::EInputActionOrigin::k_EInputActionOrigin_None;
+                sys::STEAM_INPUT_MAX_ORIGINS as usize
+            ];
+            let quantity = sys::SteamAPI_ISteamInput_GetDigitalActionOrigins(
+                self.input,
+                input_handle,
+                action_set_handle,
+                action_handle,
+                origins.as_mut_ptr(),
+            );
+            origins.truncate(quantity as usize);
+            origins.into_iter().map(InputActionOrigin).collect()
         }
     }
 
@@ -101,14 +228,261 @@ impl<Manager> Input<Manager> {
         &self,
         input_handle: sys::InputHandle_t,
         action_handle: sys::InputAnalogActionHandle_t,
-    ) -> sys::InputAnalogActionData_t {
+    ) -> AnalogActionData {
+        unsafe {
+            let data = sys::SteamAPI_ISteamInput_GetAnalogActionData(
+                self.input,
+                input_handle,
+                action_handle,
+            );
+            AnalogActionData {
+                mode: data.eMode.into(),
+                x: data.x,
+                y: data.y,
+                active: data.bActive,
+            }
+        }
+    }
+
+    /// Returns the sticks etc. the analog action is bound to in the given
+    /// action set of the controller.
+    pub fn get_analog_action_origins(
+        &self,
+        input_handle: sys::InputHandle_t,
+        action_set_handle: sys::InputActionSetHandle_t,
+        action_handle: sys::InputAnalogActionHandle_t,
+    ) -> Vec<InputActionOrigin> {
+        unsafe {
+            let mut origins = vec![
+                sys::EInputActionOrigin::k_EInputActionOrigin_None;
+                sys::STEAM_INPUT_MAX_ORIGINS as usize
+            ];
+            let quantity = sys::SteamAPI_ISteamInput_GetAnalogActionOrigins(
+                self.input,
+                input_handle,
+                action_set_handle,
+                action_handle,
+                origins.as_mut_ptr(),
+            );
+            origins.truncate(quantity as usize);
+            origins.into_iter().map(InputActionOrigin).collect()
+        }
+    }
+
+    /// Stops the momentum of an analog action bound to a trackpad set up
+    /// as a trackball, e.g. when the camera hits a wall.
+    pub fn stop_analog_action_momentum(
+        &self,
+        input_handle: sys::InputHandle_t,
+        action_handle: sys::InputAnalogActionHandle_t,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_StopAnalogActionMomentum(
+                self.input,
+                input_handle,
+                action_handle,
+            )
+        }
+    }
+
+    /// Returns the path of a PNG image of the given origin's button prompt,
+    /// matching the controller it belongs to
+    pub fn get_glyph_png_for_action_origin(
+        &self,
+        origin: InputActionOrigin,
+        size: GlyphSize,
+        style: GlyphStyle,
+    ) -> Option<PathBuf> {
+        unsafe {
+            let path = sys::SteamAPI_ISteamInput_GetGlyphPNGForActionOrigin(
+                self.input,
+                origin.0,
+                size.into(),
+                style.bits(),
+            );
+            glyph_path(path)
+        }
+    }
+
+    /// Returns the path of an SVG image of the given origin's button prompt,
+    /// matching the controller it belongs to
+    pub fn get_glyph_svg_for_action_origin(
+        &self,
+        origin: InputActionOrigin,
+        style: GlyphStyle,
+    ) -> Option<PathBuf> {
+        unsafe {
+            let path = sys::SteamAPI_ISteamInput_GetGlyphSVGForActionOrigin(
+                self.input,
+                origin.0,
+                style.bits(),
+            );
+            glyph_path(path)
+        }
+    }
+
+    /// Returns the name of the given origin in the user's language, e.g. "A Button"
+    pub fn get_string_for_action_origin(&self, origin: InputActionOrigin) -> String {
+        unsafe {
+            let name = sys::SteamAPI_ISteamInput_GetStringForActionOrigin(self.input, origin.0);
+            CStr::from_ptr(name).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Returns the localized name of the digital action as set up in the
+    /// input action manifest
+    pub fn get_string_for_digital_action_name(
+        &self,
+        action_handle: sys::InputDigitalActionHandle_t,
+    ) -> String {
+        unsafe {
+            let name =
+                sys::SteamAPI_ISteamInput_GetStringForDigitalActionName(self.input, action_handle);
+            CStr::from_ptr(name).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Returns the localized name of the analog action as set up in the
+    /// input action manifest
+    pub fn get_string_for_analog_action_name(
+        &self,
+        action_handle: sys::InputAnalogActionHandle_t,
+    ) -> String {
+        unsafe {
+            let name =
+                sys::SteamAPI_ISteamInput_GetStringForAnalogActionName(self.input, action_handle);
+            CStr::from_ptr(name).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Vibrates the controller's left and right rumble motors.
+    ///
+    /// The speeds go from 0 (off) to `u16::MAX`. On controllers without
+    /// rumble motors, e.g. the Steam Controller, this is emulated with haptics.
+    pub fn trigger_vibration(
+        &self,
+        input_handle: sys::InputHandle_t,
+        left_speed: u16,
+        right_speed: u16,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_TriggerVibration(
+                self.input,
+                input_handle,
+                left_speed,
+                right_speed,
+            )
+        }
+    }
+
+    /// Vibrates the controller's rumble motors including the trigger motors
+    /// of controllers that have them, e.g. Xbox One controllers
+    pub fn trigger_vibration_extended(
+        &self,
+        input_handle: sys::InputHandle_t,
+        left_speed: u16,
+        right_speed: u16,
+        left_trigger_speed: u16,
+        right_trigger_speed: u16,
+    ) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_TriggerVibrationExtended(
+                self.input,
+                input_handle,
+                left_speed,
+                right_speed,
+                left_trigger_speed,
+                right_trigger_speed,
+            )
+        }
+    }
+
+    /// Plays a short haptic pulse on controllers with haptics, e.g. the
+    /// DualSense or the Steam Controller trackpads.
+    ///
+    /// `intensity` and `gain_db` apply to `location`, the `other_` values to
+    /// the opposite side when `location` isn't `Both`.
+    pub fn trigger_simple_haptic_event(
+        &self,
+        input_handle: sys::InputHandle_t,
+        location: HapticLocation,
+        intensity: u8,
+        gain_db: i8,
+        other_intensity: u8,
+        other_gain_db: i8,
+    ) {
         unsafe {
-            sys::SteamAPI_ISteamInput_GetAnalogActionData(self.input, input_handle, action_handle)
+            sys::SteamAPI_ISteamInput_TriggerSimpleHapticEvent(
+                self.input,
+                input_handle,
+                location.into(),
+                intensity,
+                gain_db as _,
+                other_intensity,
+                other_gain_db as _,
+            )
         }
     }
 
-    pub fn get_motion_data(&self, input_handle: sys::InputHandle_t) -> sys::InputMotionData_t {
-        unsafe { sys::SteamAPI_ISteamInput_GetMotionData(self.input, input_handle) }
+    /// Sets the color of the controller's LED, e.g. the DualShock 4 light bar
+    pub fn set_led_color(&self, input_handle: sys::InputHandle_t, r: u8, g: u8, b: u8) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_SetLEDColor(
+                self.input,
+                input_handle,
+                r,
+                g,
+                b,
+                sys::ESteamInputLEDFlag::k_ESteamInputLEDFlag_SetColor as _,
+            )
+        }
+    }
+
+    /// Restores the LED color the user picked in the steam settings
+    pub fn reset_led_color(&self, input_handle: sys::InputHandle_t) {
+        unsafe {
+            sys::SteamAPI_ISteamInput_SetLEDColor(
+                self.input,
+                input_handle,
+                0,
+                0,
+                0,
+                sys::ESteamInputLEDFlag::k_ESteamInputLEDFlag_RestoreUserDefault as _,
+            )
+        }
+    }
+
+    /// Opens the steam overlay's binding screen for the controller.
+    ///
+    /// Returns `false` if the overlay is disabled or unavailable, e.g. when
+    /// the game isn't running in Big Picture mode.
+    pub fn show_binding_panel(&self, input_handle: sys::InputHandle_t) -> bool {
+        unsafe { sys::SteamAPI_ISteamInput_ShowBindingPanel(self.input, input_handle) }
+    }
+
+    /// Returns the type of the controller, e.g. to pick a style of button prompts
+    pub fn get_input_type_for_handle(&self, input_handle: sys::InputHandle_t) -> InputType {
+        unsafe { sys::SteamAPI_ISteamInput_GetInputTypeForHandle(self.input, input_handle).into() }
+    }
+
+    /// Returns the orientation and movement of controllers with a gyroscope
+    /// and accelerometer, e.g. for gyro aiming.
+    pub fn get_motion_data(&self, input_handle: sys::InputHandle_t) -> MotionData {
+        unsafe {
+            let data = sys::SteamAPI_ISteamInput_GetMotionData(self.input, input_handle);
+            MotionData {
+                rot_quat_x: data.rotQuatX,
+                rot_quat_y: data.rotQuatY,
+                rot_quat_z: data.rotQuatZ,
+                rot_quat_w: data.rotQuatW,
+                pos_accel_x: data.posAccelX,
+                pos_accel_y: data.posAccelY,
+                pos_accel_z: data.posAccelZ,
+                rot_vel_x: data.rotVelX,
+                rot_vel_y: data.rotVelY,
+                rot_vel_z: data.rotVelZ,
+            }
+        }
     }
 
     /// Shutdown must be called when ending use of this interface.
@@ -118,3 +492,278 @@ impl<Manager> Input<Manager> {
         }
     }
 }
+
+/// The state of a digital action, e.g. a button, see `Input::get_digital_action_data`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DigitalActionData {
+    /// Whether the action is currently pressed
+    pub state: bool,
+    /// Whether the action is bound in the controller's active action set
+    pub active: bool,
+}
+
+/// The state of an analog action, e.g. a stick, see `Input::get_analog_action_data`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnalogActionData {
+    /// How the part of the controller the action is bound to is set up
+    pub mode: InputSourceMode,
+    /// The horizontal value, from -1 to 1 for sticks
+    pub x: f32,
+    /// The vertical value, from -1 to 1 for sticks
+    pub y: f32,
+    /// Whether the action is bound in the controller's active action set
+    pub active: bool,
+}
+
+/// How the input an analog action is bound to is set up by the user
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputSourceMode {
+    None,
+    Dpad,
+    Buttons,
+    FourButtons,
+    AbsoluteMouse,
+    RelativeMouse,
+    JoystickMove,
+    JoystickMouse,
+    JoystickCamera,
+    ScrollWheel,
+    Trigger,
+    TouchMenu,
+    MouseJoystick,
+    MouseRegion,
+    RadialMenu,
+    SingleButton,
+    Switches,
+}
+
+impl From<sys::EInputSourceMode> for InputSourceMode {
+    fn from(mode: sys::EInputSourceMode) -> Self {
+        match mode {
+            sys::EInputSourceMode::k_EInputSourceMode_Dpad => InputSourceMode::Dpad,
+            sys::EInputSourceMode::k_EInputSourceMode_Buttons => InputSourceMode::Buttons,
+            sys::EInputSourceMode::k_EInputSourceMode_FourButtons => InputSourceMode::FourButtons,
+            sys::EInputSourceMode::k_EInputSourceMode_AbsoluteMouse => {
+                InputSourceMode::AbsoluteMouse
+            }
+            sys::EInputSourceMode::k_EInputSourceMode_RelativeMouse => {
+                InputSourceMode::RelativeMouse
+            }
+            sys::EInputSourceMode::k_EInputSourceMode_JoystickMove => InputSourceMode::JoystickMove,
+            sys::EInputSourceMode::k_EInputSourceMode_JoystickMouse => {
+                InputSourceMode::JoystickMouse
+            }
+            sys::EInputSourceMode::k_EInputSourceMode_JoystickCamera => {
+                InputSourceMode::JoystickCamera
+            }
+            sys::EInputSourceMode::k_EInputSourceMode_ScrollWheel => InputSourceMode::ScrollWheel,
+            sys::EInputSourceMode::k_EInputSourceMode_Trigger => InputSourceMode::Trigger,
+            sys::EInputSourceMode::k_EInputSourceMode_TouchMenu => InputSourceMode::TouchMenu,
+            sys::EInputSourceMode::k_EInputSourceMode_MouseJoystick => {
+                InputSourceMode::MouseJoystick
+            }
+            sys::EInputSourceMode::k_EInputSourceMode_MouseRegion => InputSourceMode::MouseRegion,
+            sys::EInputSourceMode::k_EInputSourceMode_RadialMenu => InputSourceMode::RadialMenu,
+            sys::EInputSourceMode::k_EInputSourceMode_SingleButton => InputSourceMode::SingleButton,
+            sys::EInputSourceMode::k_EInputSourceMode_Switches => InputSourceMode::Switches,
+            _ => InputSourceMode::None,
+        }
+    }
+}
+
+unsafe fn glyph_path(path: *const c_char) -> Option<PathBuf> {
+    if path.is_null() {
+        return None;
+    }
+    let path = CStr::from_ptr(path).to_string_lossy();
+    if path.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(path.into_owned()))
+    }
+}
+
+/// The size of a PNG glyph from `Input::get_glyph_png_for_action_origin`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GlyphSize {
+    /// 32x32 pixels
+    Small,
+    /// 128x128 pixels
+    Medium,
+    /// 256x256 pixels
+    Large,
+}
+
+impl From<GlyphSize> for sys::ESteamInputGlyphSize {
+    fn from(size: GlyphSize) -> Self {
+        match size {
+            GlyphSize::Small => sys::ESteamInputGlyphSize::k_ESteamInputGlyphSize_Small,
+            GlyphSize::Medium => sys::ESteamInputGlyphSize::k_ESteamInputGlyphSize_Medium,
+            GlyphSize::Large => sys::ESteamInputGlyphSize::k_ESteamInputGlyphSize_Large,
+        }
+    }
+}
+
+bitflags! {
+    /// The style of a glyph. `KNOCKOUT`, `LIGHT` and `DARK` select the
+    /// background, the ABXY flags change how face buttons are colored.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[repr(C)]
+    pub struct GlyphStyle: u32 {
+        const KNOCKOUT            = 0x00;
+        const LIGHT               = 0x01;
+        const DARK                = 0x02;
+        const NEUTRAL_COLOR_ABXY  = 0x10;
+        const SOLID_ABXY          = 0x20;
+    }
+}
+
+/// The motion sensor readings of a controller, see `Input::get_motion_data`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MotionData {
+    /// The orientation of the controller as a quaternion, calculated by
+    /// steam from the gyroscope
+    pub rot_quat_x: f32,
+    pub rot_quat_y: f32,
+    pub rot_quat_z: f32,
+    pub rot_quat_w: f32,
+    /// The raw acceleration of the controller, from the accelerometer
+    pub pos_accel_x: f32,
+    pub pos_accel_y: f32,
+    pub pos_accel_z: f32,
+    /// The raw angular velocity of the controller, from the gyroscope
+    pub rot_vel_x: f32,
+    pub rot_vel_y: f32,
+    pub rot_vel_z: f32,
+}
+
+/// The type of a controller, see `Input::get_input_type_for_handle`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputType {
+    Unknown,
+    SteamController,
+    XBox360Controller,
+    XBoxOneController,
+    GenericGamepad,
+    PS4Controller,
+    AppleMFiController,
+    AndroidController,
+    SwitchJoyConPair,
+    SwitchJoyConSingle,
+    SwitchProController,
+    MobileTouch,
+    PS3Controller,
+    PS5Controller,
+    SteamDeckController,
+}
+
+impl From<sys::ESteamInputType> for InputType {
+    fn from(input_type: sys::ESteamInputType) -> Self {
+        match input_type {
+            sys::ESteamInputType::k_ESteamInputType_SteamController => InputType::SteamController,
+            sys::ESteamInputType::k_ESteamInputType_XBox360Controller => {
+                InputType::XBox360Controller
+            }
+            sys::ESteamInputType::k_ESteamInputType_XBoxOneController => {
+                InputType::XBoxOneController
+            }
+            sys::ESteamInputType::k_ESteamInputType_GenericGamepad => InputType::GenericGamepad,
+            sys::ESteamInputType::k_ESteamInputType_PS4Controller => InputType::PS4Controller,
+            sys::ESteamInputType::k_ESteamInputType_AppleMFiController => {
+                InputType::AppleMFiController
+            }
+            sys::ESteamInputType::k_ESteamInputType_AndroidController => {
+                InputType::AndroidController
+            }
+            sys::ESteamInputType::k_ESteamInputType_SwitchJoyConPair => InputType::SwitchJoyConPair,
+            sys::ESteamInputType::k_ESteamInputType_SwitchJoyConSingle => {
+                InputType::SwitchJoyConSingle
+            }
+            sys::ESteamInputType::k_ESteamInputType_SwitchProController => {
+                InputType::SwitchProController
+            }
+            sys::ESteamInputType::k_ESteamInputType_MobileTouch => InputType::MobileTouch,
+            sys::ESteamInputType::k_ESteamInputType_PS3Controller => InputType::PS3Controller,
+            sys::ESteamInputType::k_ESteamInputType_PS5Controller => InputType::PS5Controller,
+            sys::ESteamInputType::k_ESteamInputType_SteamDeckController => {
+                InputType::SteamDeckController
+            }
+            _ => InputType::Unknown,
+        }
+    }
+}
+
+/// Which side of a controller a haptic event is played on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HapticLocation {
+    Left,
+    Right,
+    Both,
+}
+
+impl From<HapticLocation> for sys::EControllerHapticLocation {
+    fn from(location: HapticLocation) -> Self {
+        match location {
+            HapticLocation::Left => {
+                sys::EControllerHapticLocation::k_EControllerHapticLocation_Left
+            }
+            HapticLocation::Right => {
+                sys::EControllerHapticLocation::k_EControllerHapticLocation_Right
+            }
+            HapticLocation::Both => {
+                sys::EControllerHapticLocation::k_EControllerHapticLocation_Both
+            }
+        }
+    }
+}
+
+/// A button, stick or other part of a controller an action can be bound to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InputActionOrigin(pub(crate) sys::EInputActionOrigin);
+
+/// Called when a controller is connected, see `Input::enable_device_callbacks`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SteamInputDeviceConnected {
+    /// The handle of the connected controller
+    pub input_handle: sys::InputHandle_t,
+}
+
+unsafe impl Callback for SteamInputDeviceConnected {
+    const ID: i32 = CALLBACK_BASE_ID + 1;
+    const SIZE: i32 = ::std::mem::size_of::<sys::SteamInputDeviceConnected_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::SteamInputDeviceConnected_t);
+        SteamInputDeviceConnected {
+            input_handle: val.m_ulConnectedDeviceHandle,
+        }
+    }
+}
+
+/// Called when a controller is disconnected, see `Input::enable_device_callbacks`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SteamInputDeviceDisconnected {
+    /// The handle of the disconnected controller
+    pub input_handle: sys::InputHandle_t,
+}
+
+unsafe impl Callback for SteamInputDeviceDisconnected {
+    const ID: i32 = CALLBACK_BASE_ID + 2;
+    const SIZE: i32 = ::std::mem::size_of::<sys::SteamInputDeviceDisconnected_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::SteamInputDeviceDisconnected_t);
+        SteamInputDeviceDisconnected {
+            input_handle: val.m_ulDisconnectedDeviceHandle,
+        }
+    }
+}
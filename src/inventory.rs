@@ -0,0 +1,499 @@
+use super::*;
+
+use std::time::{Duration, SystemTime};
+
+const CALLBACK_BASE_ID: i32 = 4700;
+
+/// Access to the steam inventory interface
+///
+/// Most calls return an `InventoryResult` straight away that is filled in
+/// asynchronously, the `InventoryResultReady` callback is called with its
+/// handle once it is ready.
+pub struct Inventory<Manager> {
+    pub(crate) inventory: *mut sys::ISteamInventory,
+    pub(crate) inner: Arc<Inner<Manager>>,
+}
+
+/// The id of an item instance owned by a user
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SteamItemInstanceId(pub u64);
+
+/// The id of an item definition in the inventory schema of the app
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SteamItemDef(pub i32);
+
+/// A handle to an `InventoryResult`, used to match the result to the
+/// `InventoryResultReady` callback
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InventoryResultHandle(pub i32);
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[repr(C)]
+    pub struct InventoryItemFlags: u16 {
+        /// The item can't be traded or sold
+        const NO_TRADE = sys::ESteamItemFlags::k_ESteamItemNoTrade as u16;
+        /// The item was destroyed, traded away or had its quantity reduced
+        /// to zero
+        const REMOVED = sys::ESteamItemFlags::k_ESteamItemRemoved as u16;
+        /// The item quantity was reduced by consuming it
+        const CONSUMED = sys::ESteamItemFlags::k_ESteamItemConsumed as u16;
+    }
+}
+
+/// An item in a user's inventory
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InventoryItem {
+    pub item_id: SteamItemInstanceId,
+    pub definition: SteamItemDef,
+    pub quantity: u16,
+    pub flags: InventoryItemFlags,
+}
+
+impl<Manager> Inventory<Manager> {
+    /// Requests every item in the current user's inventory.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn get_all_items(&self) -> Option<InventoryResult<Manager>> {
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_GetAllItems(self.inventory, &mut handle) {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Requests the given items of the current user, e.g. to refresh them
+    /// after a trade.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn get_items_by_id(
+        &self,
+        item_ids: &[SteamItemInstanceId],
+    ) -> Option<InventoryResult<Manager>> {
+        let item_ids: Vec<sys::SteamItemInstanceID_t> = item_ids.iter().map(|id| id.0).collect();
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_GetItemsByID(
+                self.inventory,
+                &mut handle,
+                item_ids.as_ptr(),
+                item_ids.len() as u32,
+            ) {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Grants the current user every promo item they are eligible for, as
+    /// set up in the item definitions.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn grant_promo_items(&self) -> Option<InventoryResult<Manager>> {
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_GrantPromoItems(self.inventory, &mut handle) {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Grants the current user the given promo item if they are eligible
+    /// for it.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn add_promo_item(&self, definition: SteamItemDef) -> Option<InventoryResult<Manager>> {
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_AddPromoItem(self.inventory, &mut handle, definition.0)
+            {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Grants the current user every given promo item they are eligible
+    /// for.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn add_promo_items(
+        &self,
+        definitions: &[SteamItemDef],
+    ) -> Option<InventoryResult<Manager>> {
+        let definitions: Vec<sys::SteamItemDef_t> = definitions.iter().map(|def| def.0).collect();
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_AddPromoItems(
+                self.inventory,
+                &mut handle,
+                definitions.as_ptr(),
+                definitions.len() as u32,
+            ) {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Grants the current user the given quantities of items, for testing.
+    ///
+    /// This only works for users in the developer group of the app.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn generate_items(
+        &self,
+        items: &[(SteamItemDef, u32)],
+    ) -> Option<InventoryResult<Manager>> {
+        let definitions: Vec<sys::SteamItemDef_t> = items.iter().map(|(def, _)| def.0).collect();
+        let quantities: Vec<u32> = items.iter().map(|(_, quantity)| *quantity).collect();
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_GenerateItems(
+                self.inventory,
+                &mut handle,
+                definitions.as_ptr(),
+                quantities.as_ptr(),
+                items.len() as u32,
+            ) {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Exchanges the given quantities of the user's items for new items,
+    /// following an exchange recipe of the output item definition, e.g.
+    /// combining 10 scrap into 1 crate.
+    ///
+    /// The result contains the new items and the consumed inputs, it fails
+    /// if no recipe matches the inputs.
+    ///
+    /// Returns `None` if the request couldn't be started.
+    pub fn exchange_items(
+        &self,
+        outputs: &[(SteamItemDef, u32)],
+        inputs: &[(SteamItemInstanceId, u32)],
+    ) -> Option<InventoryResult<Manager>> {
+        let output_definitions: Vec<sys::SteamItemDef_t> =
+            outputs.iter().map(|(def, _)| def.0).collect();
+        let output_quantities: Vec<u32> = outputs.iter().map(|(_, quantity)| *quantity).collect();
+        let input_items: Vec<sys::SteamItemInstanceID_t> =
+            inputs.iter().map(|(id, _)| id.0).collect();
+        let input_quantities: Vec<u32> = inputs.iter().map(|(_, quantity)| *quantity).collect();
+        unsafe {
+            let mut handle = sys::k_SteamInventoryResultInvalid;
+            if sys::SteamAPI_ISteamInventory_ExchangeItems(
+                self.inventory,
+                &mut handle,
+                output_definitions.as_ptr(),
+                output_quantities.as_ptr(),
+                outputs.len() as u32,
+                input_items.as_ptr(),
+                input_quantities.as_ptr(),
+                inputs.len() as u32,
+            ) {
+                Some(self.result(handle))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Loads the item definitions of the app, the `InventoryDefinitionUpdate`
+    /// callback is called once they are available.
+    ///
+    /// Steam loads the definitions automatically on startup, this is only
+    /// needed to refresh them.
+    pub fn load_item_definitions(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamInventory_LoadItemDefinitions(self.inventory) }
+    }
+
+    /// Returns the ids of every item definition of the app.
+    ///
+    /// This is empty until the definitions are loaded.
+    pub fn item_definition_ids(&self) -> Vec<SteamItemDef> {
+        unsafe {
+            let mut count = 0;
+            if !sys::SteamAPI_ISteamInventory_GetItemDefinitionIDs(
+                self.inventory,
+                std::ptr::null_mut(),
+                &mut count,
+            ) {
+                return Vec::new();
+            }
+            let mut ids = vec![0; count as usize];
+            if !sys::SteamAPI_ISteamInventory_GetItemDefinitionIDs(
+                self.inventory,
+                ids.as_mut_ptr(),
+                &mut count,
+            ) {
+                return Vec::new();
+            }
+            ids.truncate(count as usize);
+            ids.into_iter().map(SteamItemDef).collect()
+        }
+    }
+
+    /// Returns a property of the item definition, e.g. `name`,
+    /// `description` or `icon_url`
+    pub fn item_definition_property(&self, definition: SteamItemDef, name: &str) -> Option<String> {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            read_property(|buffer, size| {
+                sys::SteamAPI_ISteamInventory_GetItemDefinitionProperty(
+                    self.inventory,
+                    definition.0,
+                    name.as_ptr(),
+                    buffer,
+                    size,
+                )
+            })
+        }
+    }
+
+    /// Returns the names of every property of the item definition
+    pub fn item_definition_property_names(&self, definition: SteamItemDef) -> Vec<String> {
+        let names = unsafe {
+            read_property(|buffer, size| {
+                sys::SteamAPI_ISteamInventory_GetItemDefinitionProperty(
+                    self.inventory,
+                    definition.0,
+                    std::ptr::null(),
+                    buffer,
+                    size,
+                )
+            })
+        };
+        split_property_names(names)
+    }
+
+    fn result(&self, handle: sys::SteamInventoryResult_t) -> InventoryResult<Manager> {
+        InventoryResult {
+            inventory: self.inventory,
+            _inner: self.inner.clone(),
+            handle,
+        }
+    }
+}
+
+/// The result of an inventory request.
+///
+/// The result is destroyed when dropped.
+pub struct InventoryResult<Manager> {
+    inventory: *mut sys::ISteamInventory,
+    _inner: Arc<Inner<Manager>>,
+    handle: sys::SteamInventoryResult_t,
+}
+
+unsafe impl<Manager: Send + Sync> Send for InventoryResult<Manager> {}
+
+impl<Manager> InventoryResult<Manager> {
+    /// Returns the handle of the result, see `InventoryResultReady`
+    pub fn handle(&self) -> InventoryResultHandle {
+        InventoryResultHandle(self.handle)
+    }
+
+    /// Returns whether the request succeeded, `SteamError::Pending` if
+    /// the result isn't ready yet
+    pub fn status(&self) -> Result<(), SteamError> {
+        unsafe {
+            match sys::SteamAPI_ISteamInventory_GetResultStatus(self.inventory, self.handle) {
+                sys::EResult::k_EResultOK => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    /// Returns the items in the result, or `None` if it isn't ready yet or
+    /// the request failed
+    pub fn items(&self) -> Option<Vec<InventoryItem>> {
+        unsafe {
+            let mut count = 0;
+            if !sys::SteamAPI_ISteamInventory_GetResultItems(
+                self.inventory,
+                self.handle,
+                std::ptr::null_mut(),
+                &mut count,
+            ) {
+                return None;
+            }
+            let mut items = Vec::with_capacity(count as usize);
+            if !sys::SteamAPI_ISteamInventory_GetResultItems(
+                self.inventory,
+                self.handle,
+                items.as_mut_ptr(),
+                &mut count,
+            ) {
+                return None;
+            }
+            items.set_len(count as usize);
+            Some(
+                items
+                    .into_iter()
+                    .map(|item: sys::SteamItemDetails_t| InventoryItem {
+                        item_id: SteamItemInstanceId(item.m_itemId),
+                        definition: SteamItemDef(item.m_iDefinition),
+                        quantity: item.m_unQuantity,
+                        flags: InventoryItemFlags::from_bits_truncate(item.m_unFlags),
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    /// Returns a dynamic property of the item at the given index of `items`
+    pub fn item_property(&self, index: u32, name: &str) -> Option<String> {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            read_property(|buffer, size| {
+                sys::SteamAPI_ISteamInventory_GetResultItemProperty(
+                    self.inventory,
+                    self.handle,
+                    index,
+                    name.as_ptr(),
+                    buffer,
+                    size,
+                )
+            })
+        }
+    }
+
+    /// Returns the names of the dynamic properties of the item at the given
+    /// index of `items`
+    pub fn item_property_names(&self, index: u32) -> Vec<String> {
+        let names = unsafe {
+            read_property(|buffer, size| {
+                sys::SteamAPI_ISteamInventory_GetResultItemProperty(
+                    self.inventory,
+                    self.handle,
+                    index,
+                    std::ptr::null(),
+                    buffer,
+                    size,
+                )
+            })
+        };
+        split_property_names(names)
+    }
+
+    /// Returns the server time at which the result was generated
+    pub fn timestamp(&self) -> SystemTime {
+        unsafe {
+            let time =
+                sys::SteamAPI_ISteamInventory_GetResultTimestamp(self.inventory, self.handle);
+            SystemTime::UNIX_EPOCH + Duration::from_secs(time as u64)
+        }
+    }
+}
+
+impl<Manager> Drop for InventoryResult<Manager> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::SteamAPI_ISteamInventory_DestroyResult(self.inventory, self.handle);
+        }
+    }
+}
+
+/// Reads a property string, calling `get` once for the size and once for the
+/// value
+unsafe fn read_property<F>(get: F) -> Option<String>
+where
+    F: Fn(*mut std::os::raw::c_char, *mut u32) -> bool,
+{
+    let mut size = 0;
+    if !get(std::ptr::null_mut(), &mut size) {
+        return None;
+    }
+    let mut buffer = vec![0u8; size as usize];
+    if size == 0 || !get(buffer.as_mut_ptr() as *mut _, &mut size) {
+        return None;
+    }
+    let value = CStr::from_bytes_until_nul(&buffer).ok()?;
+    Some(value.to_string_lossy().into_owned())
+}
+
+fn split_property_names(names: Option<String>) -> Vec<String> {
+    names
+        .map(|names| {
+            names
+                .split(',')
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Called when an `InventoryResult` is ready or failed
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InventoryResultReady {
+    /// The handle of the result that is ready
+    pub handle: InventoryResultHandle,
+    pub result: Result<(), SteamError>,
+}
+
+unsafe impl Callback for InventoryResultReady {
+    const ID: i32 = CALLBACK_BASE_ID;
+    const SIZE: i32 = ::std::mem::size_of::<sys::SteamInventoryResultReady_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::SteamInventoryResultReady_t);
+        InventoryResultReady {
+            handle: InventoryResultHandle(val.m_handle),
+            result: match val.m_result {
+                sys::EResult::k_EResultOK => Ok(()),
+                err => Err(err.into()),
+            },
+        }
+    }
+}
+
+/// Called in addition to `InventoryResultReady` when a result with the full
+/// inventory of the user is ready, e.g. from `Inventory::get_all_items`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InventoryFullUpdate {
+    /// The handle of the result that is ready
+    pub handle: InventoryResultHandle,
+}
+
+unsafe impl Callback for InventoryFullUpdate {
+    const ID: i32 = CALLBACK_BASE_ID + 1;
+    const SIZE: i32 = ::std::mem::size_of::<sys::SteamInventoryFullUpdate_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::SteamInventoryFullUpdate_t);
+        InventoryFullUpdate {
+            handle: InventoryResultHandle(val.m_handle),
+        }
+    }
+}
+
+/// Called when the item definitions of the app were loaded or updated
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InventoryDefinitionUpdate;
+
+unsafe impl Callback for InventoryDefinitionUpdate {
+    const ID: i32 = CALLBACK_BASE_ID + 2;
+    const SIZE: i32 = ::std::mem::size_of::<sys::SteamInventoryDefinitionUpdate_t>() as i32;
+
+    unsafe fn from_raw(_: *mut c_void) -> Self {
+        InventoryDefinitionUpdate
+    }
+}
@@ -23,9 +23,16 @@ use serde::{Deserialize, Serialize};
 
 pub use crate::app::*;
 pub use crate::callback::*;
+#[cfg(feature = "controller")]
+pub use crate::controller::*;
+#[cfg(feature = "encrypted-app-ticket")]
+pub use crate::encrypted_app_ticket::*;
 pub use crate::error::*;
 pub use crate::friends::*;
+pub use crate::html_surface::*;
+pub use crate::http::*;
 pub use crate::input::*;
+pub use crate::inventory::*;
 pub use crate::matchmaking::*;
 pub use crate::networking::*;
 pub use crate::remote_storage::*;
@@ -37,9 +44,16 @@ pub use crate::utils::*;
 
 mod app;
 mod callback;
+#[cfg(feature = "controller")]
+mod controller;
+#[cfg(feature = "encrypted-app-ticket")]
+mod encrypted_app_ticket;
 mod error;
 mod friends;
+mod html_surface;
+mod http;
 mod input;
+mod inventory;
 mod matchmaking;
 mod networking;
 pub mod networking_messages;
@@ -107,6 +121,9 @@ struct NetworkingSocketsData<Manager> {
     /// Connections to a remote listening port
     independent_connections: HashMap<sys::HSteamNetConnection, Sender<()>>,
     connection_callback: Weak<CallbackHandle<Manager>>,
+    /// Tasks waiting on a `NetConnectionStream`, woken on every `run_callbacks`
+    #[cfg(feature = "futures")]
+    wakers: Vec<std::task::Waker>,
 }
 
 unsafe impl<Manager: Send + Sync> Send for Inner<Manager> {}
@@ -169,6 +186,8 @@ impl Client<ClientManager> {
                     sockets: Default::default(),
                     independent_connections: Default::default(),
                     connection_callback: Default::default(),
+                    #[cfg(feature = "futures")]
+                    wakers: Default::default(),
                 }),
             });
             Ok((
@@ -249,6 +268,16 @@ where
                 sys::SteamAPI_ManualDispatch_FreeLastCallback(pipe);
             }
         }
+        // Messages don't generate callbacks, so give any waiting streams a
+        // chance to check their connection again.
+        #[cfg(feature = "futures")]
+        {
+            let wakers =
+                std::mem::take(&mut self.inner.networking_sockets_data.lock().unwrap().wakers);
+            for waker in wakers {
+                waker.wake();
+            }
+        }
     }
 }
 
@@ -309,7 +338,7 @@ impl<Manager> Client<Manager> {
             debug_assert!(!apps.is_null());
             Apps {
                 apps: apps,
-                _inner: self.inner.clone(),
+                inner: self.inner.clone(),
             }
         }
     }
@@ -326,6 +355,19 @@ impl<Manager> Client<Manager> {
         }
     }
 
+    /// Returns an accessor to the legacy steam controller interface
+    #[cfg(feature = "controller")]
+    pub fn controller(&self) -> Controller<Manager> {
+        unsafe {
+            let controller = sys::SteamAPI_SteamController_v008();
+            debug_assert!(!controller.is_null());
+            Controller {
+                controller,
+                _inner: self.inner.clone(),
+            }
+        }
+    }
+
     /// Returns an accessor to the steam input interface
     pub fn input(&self) -> Input<Manager> {
         unsafe {
@@ -345,7 +387,43 @@ impl<Manager> Client<Manager> {
             debug_assert!(!user.is_null());
             User {
                 user,
-                _inner: self.inner.clone(),
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
+    /// Returns an accessor to the steam HTTP interface
+    pub fn http(&self) -> Http<Manager> {
+        unsafe {
+            let http = sys::SteamAPI_SteamHTTP_v003();
+            debug_assert!(!http.is_null());
+            Http {
+                http,
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
+    /// Returns an accessor to the steam HTML surface interface
+    pub fn html_surface(&self) -> HtmlSurface<Manager> {
+        unsafe {
+            let html = sys::SteamAPI_SteamHTMLSurface_v005();
+            debug_assert!(!html.is_null());
+            HtmlSurface {
+                html,
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
+    /// Returns an accessor to the steam inventory interface
+    pub fn inventory(&self) -> Inventory<Manager> {
+        unsafe {
+            let inventory = sys::SteamAPI_SteamInventory_v003();
+            debug_assert!(!inventory.is_null());
+            Inventory {
+                inventory,
+                inner: self.inner.clone(),
             }
         }
     }
@@ -564,12 +642,16 @@ mod tests {
         let apps = client.apps();
         println!("Apps");
         println!("IsInstalled(480): {}", apps.is_app_installed(AppId(480)));
-        println!("InstallDir(480): {}", apps.app_install_dir(AppId(480)));
+        println!(
+            "InstallDir(480): {}",
+            apps.app_install_dir(AppId(480)).display()
+        );
         println!("BuildId: {}", apps.app_build_id());
         println!("AppOwner: {:?}", apps.app_owner());
         println!("Langs: {:?}", apps.available_game_languages());
         println!("Lang: {}", apps.current_game_language());
         println!("Beta: {:?}", apps.current_beta_name());
+        println!("CommandLine: {}", apps.launch_command_line());
 
         let friends = client.friends();
         println!("Friends");
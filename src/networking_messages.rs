@@ -127,7 +127,9 @@ impl<Manager: 'static> NetworkingMessages<Manager> {
     /// let networking_messages = client.networking_messages();
     ///
     /// // Accept all new connections
-    /// networking_messages.session_request_callback(|request| request.accept());
+    /// networking_messages.session_request_callback(|request| {
+    ///     request.accept();
+    /// });
     ///
     /// let _received = networking_messages.receive_messages_on_channel(0, 10);
     /// ```
@@ -201,6 +203,45 @@ impl<Manager: 'static> NetworkingMessages<Manager> {
         }
     }
 
+    /// Call this in response to a `NetworkingMessagesSessionRequest` callback to accept the session.
+    ///
+    /// Only needed when registering the callback manually, `SessionRequest::accept` does this for you.
+    /// Returns false if there is no session with the user pending or otherwise.
+    pub fn accept_session_with_user(&self, user: &NetworkingIdentity) -> bool {
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingMessages_AcceptSessionWithUser(self.net, user.as_ptr())
+        }
+    }
+
+    /// Call this when you're done talking to a user to immediately free up resources under-the-hood.
+    /// If the remote user tries to send data to you again, another `NetworkingMessagesSessionRequest`
+    /// callback will be posted.
+    ///
+    /// Note that sessions that go unused for a few minutes are automatically timed out.
+    ///
+    /// Returns false if there was no session with the user.
+    pub fn close_session_with_user(&self, user: &NetworkingIdentity) -> bool {
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingMessages_CloseSessionWithUser(self.net, user.as_ptr())
+        }
+    }
+
+    /// Call this when you're done talking to a user on a specific channel.  Once all
+    /// open channels to a user have been closed, the open session to the user will be
+    /// closed, and any new data from this user will trigger a
+    /// `NetworkingMessagesSessionRequest` callback.
+    ///
+    /// Returns false if there was no open channel with the user.
+    pub fn close_channel_with_user(&self, user: &NetworkingIdentity, channel: u32) -> bool {
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingMessages_CloseChannelWithUser(
+                self.net,
+                user.as_ptr(),
+                channel as i32,
+            )
+        }
+    }
+
     /// Register a callback that will be called whenever a connection fails to be established.
     ///
     /// Requires regularly calling [`SingleClient.run_callbacks()`](../struct.SingleClient.html#method.run_callbacks).
@@ -237,13 +278,20 @@ impl<Manager> SessionRequestBuilder<Manager> {
         self.inner.upgrade().map(|inner| SessionRequest {
             remote,
             messages: self.message,
+            is_handled: false,
             _inner: inner,
         })
     }
 }
 
-struct NetworkingMessagesSessionRequest {
-    remote: NetworkingIdentity,
+/// Posted when a remote host is sending us a message, and we do not already have a session with them.
+///
+/// Prefer `NetworkingMessages::session_request_callback`, which allows accepting the
+/// session directly. When registering this callback yourself, accept the session
+/// with `NetworkingMessages::accept_session_with_user`.
+#[derive(Clone, Debug)]
+pub struct NetworkingMessagesSessionRequest {
+    pub remote: NetworkingIdentity,
 }
 
 unsafe impl Callback for NetworkingMessagesSessionRequest {
@@ -257,7 +305,10 @@ unsafe impl Callback for NetworkingMessagesSessionRequest {
     }
 }
 
-struct NetworkingMessagesSessionFailed {
+/// Posted when we fail to establish a connection, or we detect that communications
+/// have been disrupted in an unusual way.
+#[derive(Clone, Debug)]
+pub struct NetworkingMessagesSessionFailed {
     pub info: NetConnectionInfo,
 }
 
@@ -279,6 +330,7 @@ unsafe impl Callback for NetworkingMessagesSessionFailed {
 pub struct SessionRequest<Manager> {
     remote: NetworkingIdentity,
     messages: *mut sys::ISteamNetworkingMessages,
+    is_handled: bool,
     _inner: Arc<Inner<Manager>>,
 }
 
@@ -292,12 +344,22 @@ impl<Manager> SessionRequest<Manager> {
     }
 
     /// Accept the connection.
+    ///
+    /// Use `try_accept` to find out whether accepting succeeded.
     pub fn accept(self) {
+        self.try_accept();
+    }
+
+    /// Accept the connection.
+    ///
+    /// Returns false if there is no session with the user pending or otherwise.
+    pub fn try_accept(mut self) -> bool {
+        self.is_handled = true;
         unsafe {
             sys::SteamAPI_ISteamNetworkingMessages_AcceptSessionWithUser(
                 self.messages,
                 self.remote.as_ptr(),
-            );
+            )
         }
     }
 
@@ -308,6 +370,7 @@ impl<Manager> SessionRequest<Manager> {
 
     /// Reject the connection without consuming self, useful for implementing [`Drop`]
     fn reject_inner(&mut self) {
+        self.is_handled = true;
         unsafe {
             sys::SteamAPI_ISteamNetworkingMessages_CloseSessionWithUser(
                 self.messages,
@@ -319,6 +382,8 @@ impl<Manager> SessionRequest<Manager> {
 
 impl<Manager> Drop for SessionRequest<Manager> {
     fn drop(&mut self) {
-        self.reject_inner();
+        if !self.is_handled {
+            self.reject_inner();
+        }
     }
 }
@@ -4,11 +4,11 @@ use crate::networking_types::{
     NetworkingAvailabilityError, NetworkingConfigEntry, NetworkingIdentity, NetworkingMessage,
     SendFlags, SteamIpAddr,
 };
-use crate::{CallbackHandle, Inner, SResult};
+use crate::{CallbackHandle, Inner, SResult, SteamError};
 #[cfg(test)]
 use serial_test_derive::serial;
 use std::convert::TryInto;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::net::SocketAddr;
 use std::sync::mpsc::Receiver;
 use std::sync::Arc;
@@ -253,6 +253,24 @@ impl<Manager: 'static> NetworkingSockets<Manager> {
         unsafe { sys::SteamAPI_ISteamNetworkingSockets_InitAuthentication(self.sockets).try_into() }
     }
 
+    /// Send one or more messages without copying the message payload.
+    ///
+    /// Unlike `ListenSocket::send_messages`, the messages may target any connection
+    /// created through this interface, including connections created with
+    /// `connect_by_ip_address` or `connect_p2p`. Allocate the messages with
+    /// `NetworkingUtils::allocate_message` and set the target with
+    /// `NetworkingMessage::set_connection` before sending.
+    ///
+    /// The library takes ownership of the messages. The result at each index
+    /// corresponds to the message at the same index and contains either the
+    /// assigned message number or the reason the message could not be sent.
+    pub fn send_messages(
+        &self,
+        messages: impl IntoIterator<Item = NetworkingMessage<Manager>>,
+    ) -> Vec<SResult<MessageNumber>> {
+        send_messages(self.sockets, messages)
+    }
+
     /// Create a new poll group.
     ///
     /// You should destroy the poll group when you are done using DestroyPollGroup
@@ -364,28 +382,35 @@ impl<Manager: 'static> ListenSocket<Manager> {
         &self,
         messages: impl IntoIterator<Item = NetworkingMessage<Manager>>,
     ) -> Vec<SResult<MessageNumber>> {
-        let messages: Vec<_> = messages.into_iter().map(|x| x.take_message()).collect();
-        let mut results = vec![0; messages.len()];
-        unsafe {
-            sys::SteamAPI_ISteamNetworkingSockets_SendMessages(
-                self.inner.sockets,
-                messages.len() as _,
-                messages.as_ptr(),
-                results.as_mut_ptr(),
-            );
-            // Error codes are returned as negative numbers, while positive numbers are message numbers
-            results
-                .into_iter()
-                .map(|x| {
-                    if x >= 0 {
-                        Ok(MessageNumber(x as u64))
-                    } else {
-                        Err((-x).try_into().expect("invalid error code"))
-                    }
-                })
-                .collect()
-        }
+        send_messages(self.inner.sockets, messages)
+    }
+}
+
+fn send_messages<Manager>(
+    sockets: *mut sys::ISteamNetworkingSockets,
+    messages: impl IntoIterator<Item = NetworkingMessage<Manager>>,
+) -> Vec<SResult<MessageNumber>> {
+    let messages: Vec<_> = messages.into_iter().map(|x| x.take_message()).collect();
+    let mut results = vec![0; messages.len()];
+    unsafe {
+        sys::SteamAPI_ISteamNetworkingSockets_SendMessages(
+            sockets,
+            messages.len() as _,
+            messages.as_ptr(),
+            results.as_mut_ptr(),
+        );
     }
+    // Error codes are returned as negative numbers, while positive numbers are message numbers
+    results
+        .into_iter()
+        .map(|x| {
+            if x >= 0 {
+                Ok(MessageNumber(x as u64))
+            } else {
+                Err((-x).try_into().expect("invalid error code"))
+            }
+        })
+        .collect()
 }
 
 /// Inner struct that keeps sockets alive as long as there is still a connection alive
@@ -707,6 +732,80 @@ impl<Manager: 'static> NetConnection<Manager> {
         }
     }
 
+    /// Returns a very detailed, human readable description of the connection
+    /// status, including the status of each lane. Useful to dump into logs
+    /// or attach to bug reports.
+    ///
+    /// Returns `Err(InvalidHandle)` if the connection handle is invalid.
+    pub fn detailed_status(&self) -> Result<String, InvalidHandle> {
+        let mut buffer = vec![0u8; 2048];
+        loop {
+            let result = unsafe {
+                sys::SteamAPI_ISteamNetworkingSockets_GetDetailedConnectionStatus(
+                    self.sockets,
+                    self.handle,
+                    buffer.as_mut_ptr() as _,
+                    buffer.len() as _,
+                )
+            };
+            match result {
+                // Positive values are the size of the buffer that is needed
+                size if size > 0 => buffer.resize(size as usize, 0),
+                0 => {
+                    let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+                    buffer.truncate(len);
+                    return Ok(String::from_utf8_lossy(&buffer).into_owned());
+                }
+                _ => return Err(InvalidHandle),
+            }
+        }
+    }
+
+    /// Configure multiple outbound messages streams ("lanes") on a connection, and
+    /// control head-of-line blocking between them.  Messages within a given lane
+    /// are always sent in the order they are queued, but messages from different
+    /// lanes may be sent out of order.  Each lane has its own message number
+    /// sequence.  The first message sent on each lane will be assigned the number 1.
+    ///
+    /// Each lane has a "priority".  Lower priority lanes will only be processed
+    /// when all higher-priority lanes are empty.  The magnitudes of the priority
+    /// values are not relevant, only their sort order.  Higher numeric values
+    /// take priority over lower numeric values.
+    ///
+    /// Each lane also is assigned a weight, which controls the approximate proportion
+    /// of the bandwidth that will be consumed by the lane, relative to other lanes
+    /// of the same priority.  (This is assuming the lane stays busy.  An idle lane
+    /// does not build up "credits" to be be spent once a message is queued.)
+    /// This value is only meaningful as a proportion, relative to other lanes with
+    /// the same priority.
+    ///
+    /// `priorities` and `weights` must have the same length, which is the number of
+    /// lanes to configure, otherwise `SteamError::InvalidParameter` is returned. Use
+    /// `NetworkingMessage::set_lane` to pick the lane of a message.
+    ///
+    /// Returns:
+    /// - k_EResultNoConnection: bad connection handle
+    /// - k_EResultInvalidParam: Invalid number of lanes, bad weights, or you tried to reduce the number of lanes
+    /// - k_EResultInvalidState: Connection is already dead, etc
+    pub fn configure_lanes(&self, priorities: &[i32], weights: &[u16]) -> SResult<()> {
+        if priorities.len() != weights.len() {
+            return Err(SteamError::InvalidParameter);
+        }
+        let result = unsafe {
+            sys::SteamAPI_ISteamNetworkingSockets_ConfigureConnectionLanes(
+                self.sockets,
+                self.handle,
+                priorities.len() as _,
+                priorities.as_ptr(),
+                weights.as_ptr(),
+            )
+        };
+        match result {
+            sys::EResult::k_EResultOK => Ok(()),
+            error => Err(error.into()),
+        }
+    }
+
     /// Fetch connection name.  Returns false if handle is invalid
     pub fn connection_name(&self) -> Result<(), InvalidHandle> {
         unimplemented!()
@@ -807,29 +906,39 @@ impl<Manager: 'static> NetConnection<Manager> {
     }
 }
 
+impl<Manager> NetConnection<Manager> {
+    /// Closes the connection without consuming it, the handle must not be used
+    /// afterwards
+    fn close_unhandled(&mut self, debug_string: &CStr, enable_linger: bool) -> bool {
+        let was_successful = unsafe {
+            sys::SteamAPI_ISteamNetworkingSockets_CloseConnection(
+                self.sockets,
+                self.handle,
+                NetConnectionEnd::AppGeneric.into(),
+                debug_string.as_ptr(),
+                enable_linger,
+            )
+        };
+
+        if self.socket.is_none() {
+            self.inner
+                .networking_sockets_data
+                .lock()
+                .unwrap()
+                .independent_connections
+                .remove(&self.handle)
+                .expect("internal connection was removed before being dropped");
+        }
+        self.is_handled = true;
+        was_successful
+    }
+}
+
 impl<Manager> Drop for NetConnection<Manager> {
     fn drop(&mut self) {
         if !self.is_handled {
             let debug_string = CString::new("Handle was dropped").unwrap();
-            let _was_successful = unsafe {
-                sys::SteamAPI_ISteamNetworkingSockets_CloseConnection(
-                    self.sockets,
-                    self.handle,
-                    NetConnectionEnd::AppGeneric.into(),
-                    debug_string.as_ptr(),
-                    false,
-                )
-            };
-
-            if self.socket.is_none() {
-                self.inner
-                    .networking_sockets_data
-                    .lock()
-                    .unwrap()
-                    .independent_connections
-                    .remove(&self.handle)
-                    .expect("internal connection was removed before being dropped");
-            }
+            self.close_unhandled(&debug_string, false);
         }
     }
 }
@@ -877,6 +986,211 @@ impl<Manager> Drop for NetPollGroup<Manager> {
 #[error("operation was unsuccessful an invalid handle was returned")]
 pub struct InvalidHandle;
 
+/// An adapter that exposes a reliable `NetConnection` as a byte stream.
+///
+/// Writes are sent as reliable messages and reads return the payload of the
+/// received messages in order, so existing stream based protocols (for example
+/// length delimited codecs) can run over steam networking unchanged. Message
+/// boundaries are not preserved.
+///
+/// Reads return end of file once the peer closed the connection, and a
+/// `ConnectionReset` error if it was dropped because of a problem.
+///
+/// Steam doesn't notify about incoming messages, so pending tasks are woken
+/// each time `SingleClient::run_callbacks` is called. Make sure to keep calling it.
+#[cfg(feature = "futures")]
+pub struct NetConnectionStream<Manager> {
+    connection: NetConnection<Manager>,
+    pending: Option<NetworkingMessage<Manager>>,
+    offset: usize,
+    linger: bool,
+}
+
+#[cfg(feature = "futures")]
+impl<Manager: 'static> NetConnectionStream<Manager> {
+    /// Wraps a connection, which should be connected or about to connect.
+    ///
+    /// Closing the stream closes the connection, lingering until the data that
+    /// was already written is sent, see `linger`.
+    pub fn new(connection: NetConnection<Manager>) -> Self {
+        NetConnectionStream {
+            connection,
+            pending: None,
+            offset: 0,
+            linger: true,
+        }
+    }
+
+    /// Sets whether closing the stream waits for the written data to be sent
+    /// before the connection is closed, on by default.
+    ///
+    /// Without lingering, data that wasn't sent yet is discarded.
+    pub fn linger(mut self, linger: bool) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    /// Returns a reference to the wrapped connection
+    pub fn connection(&self) -> &NetConnection<Manager> {
+        &self.connection
+    }
+
+    /// Returns the wrapped connection, discarding any partially read message
+    pub fn into_inner(self) -> NetConnection<Manager> {
+        self.connection
+    }
+
+    fn register_waker(&self, cx: &std::task::Context<'_>) {
+        let mut data = self
+            .connection
+            .inner
+            .networking_sockets_data
+            .lock()
+            .unwrap();
+        // Tasks polled several times between two `run_callbacks` only need to be woken once
+        if !data.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            data.wakers.push(cx.waker().clone());
+        }
+    }
+
+    /// Returns `None` while the connection is open, `Ok` if it was closed cleanly and an
+    /// error if it was dropped because of a problem.
+    fn closed(&self) -> Option<std::io::Result<()>> {
+        use sys::ESteamNetworkingConnectionState::*;
+        unsafe {
+            let mut info: sys::SteamNetConnectionInfo_t = std::mem::zeroed();
+            if !sys::SteamAPI_ISteamNetworkingSockets_GetConnectionInfo(
+                self.connection.sockets,
+                self.connection.handle,
+                &mut info,
+            ) {
+                return Some(Ok(()));
+            }
+            match info.m_eState {
+                k_ESteamNetworkingConnectionState_None
+                | k_ESteamNetworkingConnectionState_ClosedByPeer => Some(Ok(())),
+                k_ESteamNetworkingConnectionState_ProblemDetectedLocally => {
+                    let debug = CStr::from_ptr(info.m_szEndDebug.as_ptr());
+                    Some(Err(std::io::Error::new(
+                        std::io::ErrorKind::ConnectionReset,
+                        debug.to_string_lossy().into_owned(),
+                    )))
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+fn to_io_error(error: crate::SteamError) -> std::io::Error {
+    use std::io::ErrorKind;
+    let kind = match error {
+        crate::SteamError::NoConnection => ErrorKind::BrokenPipe,
+        crate::SteamError::InvalidState => ErrorKind::NotConnected,
+        _ => ErrorKind::Other,
+    };
+    std::io::Error::new(kind, error)
+}
+
+#[cfg(feature = "futures")]
+impl<Manager: 'static> futures_io::AsyncRead for NetConnectionStream<Manager> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        use std::task::Poll;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        loop {
+            if let Some(message) = &self.pending {
+                let data = &message.data()[self.offset..];
+                if !data.is_empty() {
+                    let len = data.len().min(buf.len());
+                    buf[..len].copy_from_slice(&data[..len]);
+                    self.offset += len;
+                    return Poll::Ready(Ok(len));
+                }
+            }
+            self.pending = None;
+            self.offset = 0;
+
+            match self.connection.receive_messages(1).pop() {
+                Some(message) => self.pending = Some(message),
+                None => {
+                    return match self.closed() {
+                        Some(result) => Poll::Ready(result.map(|()| 0)),
+                        None => {
+                            self.register_waker(cx);
+                            Poll::Pending
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<Manager: 'static> futures_io::AsyncWrite for NetConnectionStream<Manager> {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        use std::task::Poll;
+        let len = buf
+            .len()
+            .min(sys::k_cbMaxSteamNetworkingSocketsMessageSizeSend as usize);
+        match self
+            .connection
+            .send_message(&buf[..len], SendFlags::RELIABLE)
+        {
+            Ok(_) => Poll::Ready(Ok(len)),
+            // The send buffer is full, try again once steam had a chance to send some data
+            Err(crate::SteamError::LimitExceeded) => {
+                self.register_waker(cx);
+                Poll::Pending
+            }
+            Err(error) => Poll::Ready(Err(to_io_error(error))),
+        }
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        use std::task::Poll;
+        match self.connection.flush_messages() {
+            // Flushing a connection that isn't connected yet has no effect
+            Ok(()) | Err(crate::SteamError::Ignored) => Poll::Ready(Ok(())),
+            Err(error) => Poll::Ready(Err(to_io_error(error))),
+        }
+    }
+
+    fn poll_close(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        use std::task::Poll;
+        if self.connection.is_handled {
+            return Poll::Ready(Ok(()));
+        }
+        if let Poll::Ready(Err(error)) = self.as_mut().poll_flush(cx) {
+            // The connection is closed either way so it isn't leaked
+            self.connection
+                .close_unhandled(&CString::new("Stream was closed").unwrap(), false);
+            return Poll::Ready(Err(error));
+        }
+        let linger = self.linger;
+        self.connection
+            .close_unhandled(&CString::new("Stream was closed").unwrap(), linger);
+        Poll::Ready(Ok(()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
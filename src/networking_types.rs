@@ -6,10 +6,14 @@ use crate::networking_sockets::{InnerSocket, NetConnection};
 use crate::networking_types::NetConnectionError::UnhandledType;
 use crate::{Callback, Inner, SResult, SteamId};
 use std::convert::{TryFrom, TryInto};
-use std::ffi::{c_void, CString};
+use std::ffi::{c_void, CStr, CString};
 use std::fmt::{Debug, Display, Formatter};
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::hash::{Hash, Hasher};
+use std::net::{
+    AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
+};
 use std::panic::catch_unwind;
+use std::str::FromStr;
 use std::sync::Arc;
 use steamworks_sys as sys;
 
@@ -1031,7 +1035,7 @@ impl NetConnectionInfo {
 
     pub fn end_reason(&self) -> Option<NetConnectionEnd> {
         if self.inner.m_eEndReason
-            == sys::ESteamNetConnectionEnd::k_ESteamNetConnectionEnd_Invalid as _
+            == sys::ESteamNetConnectionEnd::k_ESteamNetConnectionEnd_Invalid as i32
         {
             None
         } else {
@@ -1314,7 +1318,7 @@ impl NetworkingConfigEntry {
     }
 
     pub fn new_float(value_type: NetworkingConfigValue, value: f32) -> Self {
-        debug_assert_eq!(value_type.data_type(), NetworkingConfigDataType::Int64);
+        debug_assert_eq!(value_type.data_type(), NetworkingConfigDataType::Float);
 
         let mut config = Self::new_uninitialized_config_value();
         unsafe {
@@ -1416,14 +1420,42 @@ impl NetworkingIdentity {
         }
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn ip_addr(&self) -> Option<SteamIpAddr> {
+    /// Returns the ip address if this is an ip identity
+    pub fn ip_addr(&self) -> Option<SocketAddr> {
         unsafe {
             let ip = sys::SteamAPI_SteamNetworkingIdentity_GetIPAddr(self.as_ptr() as *mut _);
             if ip.is_null() {
                 None
             } else {
-                Some(SteamIpAddr { inner: (*ip) })
+                Some(SteamIpAddr { inner: (*ip) }.to_socket_addr())
+            }
+        }
+    }
+
+    /// Set an arbitrary string as identity, at most 31 bytes long.
+    ///
+    /// Returns false if the string is too long or contains a nul byte.
+    pub fn set_generic_string(&mut self, string: &str) -> bool {
+        match CString::new(string) {
+            Ok(string) => unsafe {
+                sys::SteamAPI_SteamNetworkingIdentity_SetGenericString(
+                    self.as_mut_ptr(),
+                    string.as_ptr(),
+                )
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the string if this is a generic string identity
+    pub fn generic_string(&self) -> Option<String> {
+        unsafe {
+            let string =
+                sys::SteamAPI_SteamNetworkingIdentity_GetGenericString(self.as_ptr() as *mut _);
+            if string.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(string).to_string_lossy().into_owned())
             }
         }
     }
@@ -1465,13 +1497,20 @@ impl NetworkingIdentity {
                     format!("ip:{}", ip)
                 }
                 sys::ESteamNetworkingIdentityType::k_ESteamNetworkingIdentityType_GenericString => {
-                    unimplemented!()
+                    let string =
+                        CStr::from_ptr(self.inner.__bindgen_anon_1.m_szGenericString.as_ptr());
+                    format!("str:{}", string.to_string_lossy())
                 }
                 sys::ESteamNetworkingIdentityType::k_ESteamNetworkingIdentityType_GenericBytes => {
-                    unimplemented!()
+                    let len = (self.inner.m_cbSize as usize).min(32);
+                    let bytes = &self.inner.__bindgen_anon_1.m_genericBytes[..len];
+                    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                    format!("gen:{}", hex)
                 }
                 sys::ESteamNetworkingIdentityType::k_ESteamNetworkingIdentityType_UnknownType => {
-                    unimplemented!()
+                    CStr::from_ptr(self.inner.__bindgen_anon_1.m_szUnknownRawString.as_ptr())
+                        .to_string_lossy()
+                        .into_owned()
                 }
                 ty => format!("bad_type:{}", ty as u32),
             }
@@ -1499,12 +1538,79 @@ impl From<sys::SteamNetworkingIdentity> for NetworkingIdentity {
     }
 }
 
+impl Display for NetworkingIdentity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.debug_string())
+    }
+}
+
+/// Parses the format returned by `Display`, e.g. `steamid:76561197960287930`,
+/// `ip:192.168.0.1:27015` or `str:my-server`.
+impl FromStr for NetworkingIdentity {
+    type Err = NetworkingIdentityParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut identity = Self::new();
+        match s.split_once(':') {
+            Some(("steamid", id)) => {
+                let id = id
+                    .parse()
+                    .map_err(|_| NetworkingIdentityParseError::InvalidValue)?;
+                identity.set_steam_id(SteamId(id));
+            }
+            Some(("ip", ip)) => {
+                let ip: SteamIpAddr = ip
+                    .parse()
+                    .map_err(|_| NetworkingIdentityParseError::InvalidValue)?;
+                identity.set_ip_addr(ip.to_socket_addr());
+            }
+            Some(("str", string)) => {
+                if !identity.set_generic_string(string) {
+                    return Err(NetworkingIdentityParseError::InvalidValue);
+                }
+            }
+            _ => return Err(NetworkingIdentityParseError::UnknownType),
+        }
+        Ok(identity)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NetworkingIdentityParseError {
+    #[error("unknown or unsupported identity type")]
+    UnknownType,
+    #[error("invalid identity value")]
+    InvalidValue,
+}
+
+impl PartialEq for NetworkingIdentity {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            sys::SteamAPI_SteamNetworkingIdentity_IsEqualTo(self.as_ptr() as *mut _, other.as_ptr())
+        }
+    }
+}
+
+impl Eq for NetworkingIdentity {}
+
+impl Hash for NetworkingIdentity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.debug_string().hash(state);
+    }
+}
+
 impl From<SteamId> for NetworkingIdentity {
     fn from(id: SteamId) -> Self {
         Self::new_steam_id(id)
     }
 }
 
+impl From<SocketAddr> for NetworkingIdentity {
+    fn from(addr: SocketAddr) -> Self {
+        Self::new_ip(addr)
+    }
+}
+
 impl Default for NetworkingIdentity {
     fn default() -> Self {
         Self::new()
@@ -1535,7 +1641,7 @@ impl<Manager> NetworkingMessage<Manager> {
     /// Set the target connection for the connection.
     /// Make sure you don't close or drop the `NetConnection` before sending your message.
     ///
-    /// Use this with `ListenSocket::send_messages` for efficient sending.
+    /// Use this with `NetworkingSockets::send_messages` or `ListenSocket::send_messages` for efficient sending.
     pub fn set_connection(&mut self, connection: &NetConnection<Manager>) {
         unsafe { (*self.message).m_conn = connection.handle }
     }
@@ -1608,6 +1714,21 @@ impl<Manager> NetworkingMessage<Manager> {
         }
     }
 
+    /// The lane the message was received on or should be sent on.
+    /// See `NetConnection::configure_lanes`.
+    pub fn lane(&self) -> u16 {
+        unsafe { (*self.message).m_idxLane }
+    }
+
+    /// Sets the lane the message is sent on, 0 by default.
+    ///
+    /// The lane must have been configured with `NetConnection::configure_lanes`.
+    pub fn set_lane(&mut self, lane: u16) {
+        unsafe {
+            (*self.message).m_idxLane = lane;
+        }
+    }
+
     /// Message payload
     pub fn data(&self) -> &[u8] {
         unsafe {
@@ -1711,12 +1832,15 @@ pub enum MessageError {
     BufferAlreadySet,
 }
 
+/// A safe wrapper for SteamNetworkingIPAddr, an IPv4 or IPv6 address with port.
+///
+/// IPv4 addresses are stored as IPv4-mapped IPv6 addresses, but are converted back
+/// to `SocketAddr::V4` by `to_socket_addr`.
 #[derive(Copy, Clone)]
-pub(crate) struct SteamIpAddr {
+pub struct SteamIpAddr {
     inner: sys::SteamNetworkingIPAddr,
 }
 
-#[allow(dead_code)]
 impl SteamIpAddr {
     pub fn new() -> Self {
         unsafe {
@@ -1786,11 +1910,28 @@ impl SteamIpAddr {
         unsafe { sys::SteamAPI_SteamNetworkingIPAddr_IsIPv4(self.as_ptr() as *mut _) }
     }
 
-    pub fn as_ptr(&self) -> *const sys::SteamNetworkingIPAddr {
+    pub fn port(&self) -> u16 {
+        self.inner.m_port
+    }
+
+    pub fn to_socket_addr(&self) -> SocketAddr {
+        unsafe {
+            if self.is_ipv4() {
+                let ip = Ipv4Addr::from(self.inner.__bindgen_anon_1.m_ipv4.m_ip);
+                SocketAddr::new(ip.into(), self.port())
+            } else {
+                let ip = Ipv6Addr::from(self.inner.__bindgen_anon_1.m_ipv6);
+                SocketAddr::new(ip.into(), self.port())
+            }
+        }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const sys::SteamNetworkingIPAddr {
         &self.inner
     }
 
-    pub fn as_mut_ptr(&mut self) -> *mut sys::SteamNetworkingIPAddr {
+    #[allow(dead_code)]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut sys::SteamNetworkingIPAddr {
         &mut self.inner
     }
 
@@ -1867,6 +2008,33 @@ impl PartialEq for SteamIpAddr {
 
 impl Eq for SteamIpAddr {}
 
+impl Hash for SteamIpAddr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_socket_addr().hash(state);
+    }
+}
+
+/// Parses an address with port, or an address without port in which case the port is 0
+impl FromStr for SteamIpAddr {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<SocketAddr>() {
+            Ok(addr) => Ok(addr.into()),
+            Err(err) => s
+                .parse::<IpAddr>()
+                .map(|ip| Self::new_ip(ip, 0))
+                .map_err(|_| err),
+        }
+    }
+}
+
+impl From<SteamIpAddr> for SocketAddr {
+    fn from(ip: SteamIpAddr) -> Self {
+        ip.to_socket_addr()
+    }
+}
+
 impl From<SocketAddr> for SteamIpAddr {
     fn from(ip: SocketAddr) -> Self {
         let mut steam_ip = Self::new();
@@ -1917,6 +2085,38 @@ mod tests {
         assert_eq!(&ip.to_string(true), "192.168.0.123:5555");
     }
 
+    #[test]
+    fn test_ip_socket_addr_roundtrip() {
+        let v4: SocketAddr = "10.0.0.1:27015".parse().unwrap();
+        assert_eq!(SteamIpAddr::from(v4).to_socket_addr(), v4);
+        let v6: SocketAddr = "[2001:db8::1]:27015".parse().unwrap();
+        assert_eq!(SteamIpAddr::from(v6).to_socket_addr(), v6);
+    }
+
+    #[test]
+    fn test_parse_ip() {
+        let ip: SteamIpAddr = "192.168.0.1:80".parse().unwrap();
+        assert_eq!(format!("{}", ip), "192.168.0.1:80");
+        let ip: SteamIpAddr = "192.168.0.1".parse().unwrap();
+        assert_eq!(ip.port(), 0);
+        assert_eq!(
+            ip,
+            SteamIpAddr::new_ip(Ipv4Addr::new(192, 168, 0, 1).into(), 0)
+        );
+        assert!("not an ip".parse::<SteamIpAddr>().is_err());
+    }
+
+    #[test]
+    fn test_network_identity_parse() {
+        let id: NetworkingIdentity = "steamid:123456".parse().unwrap();
+        assert_eq!(id, NetworkingIdentity::new_steam_id(SteamId(123456)));
+        let id: NetworkingIdentity = "ip:192.168.0.5:1234".parse().unwrap();
+        assert_eq!(id.to_string(), "ip:192.168.0.5:1234");
+        let id: NetworkingIdentity = "str:server".parse().unwrap();
+        assert_eq!(id.generic_string().as_deref(), Some("server"));
+        assert!("foo:bar".parse::<NetworkingIdentity>().is_err());
+    }
+
     #[test]
     fn test_network_identity_steam_id() {
         let id = NetworkingIdentity::new_steam_id(SteamId(123456));
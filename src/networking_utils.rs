@@ -1,7 +1,10 @@
-use crate::networking_types::{NetworkingAvailabilityResult, NetworkingMessage};
+use crate::networking_types::{
+    NetworkingAvailabilityResult, NetworkingConfigDataType, NetworkingConfigValue,
+    NetworkingMessage,
+};
 use crate::{register_callback, Callback, Inner};
 use std::convert::TryInto;
-use std::ffi::{c_void, CStr};
+use std::ffi::{c_void, CStr, CString};
 use std::sync::Arc;
 
 use steamworks_sys as sys;
@@ -97,6 +100,346 @@ impl<Manager> NetworkingUtils<Manager> {
         }
     }
 
+    /// Return location info for the current host.  Returns the approximate
+    /// age of the data, in seconds, or `None` if no data is available.
+    ///
+    /// It takes a few seconds to initialize access to the relay network.  If
+    /// you call this very soon after calling `init_relay_network_access`,
+    /// the data may not be available yet.
+    ///
+    /// This always return the most up-to-date information we have available
+    /// right now, even if we are in the middle of re-calculating ping times.
+    pub fn local_ping_location(&self) -> Option<(NetworkPingLocation, f32)> {
+        unsafe {
+            let mut location = NetworkPingLocation {
+                inner: std::mem::zeroed(),
+            };
+            let age = sys::SteamAPI_ISteamNetworkingUtils_GetLocalPingLocation(
+                self.utils,
+                &mut location.inner,
+            );
+            if age < 0.0 {
+                None
+            } else {
+                Some((location, age))
+            }
+        }
+    }
+
+    /// Estimate the round-trip latency between two arbitrary locations, in
+    /// milliseconds.  This is a conservative estimate, based on routing through
+    /// the relay network.  For most basic relayed connections, this ping time
+    /// will be pretty accurate, since it will be based on the route likely to
+    /// be actually used.
+    ///
+    /// If a direct IP route is used (perhaps via NAT traversal), then the route
+    /// will be different, and the ping time might be better.  Or it might actually
+    /// be a bit worse!  Standard IP routing is frequently suboptimal!
+    ///
+    /// But even in this case, the estimate obtained using this method is a
+    /// reasonable upper bound on the ping time.  (Also it has the advantage
+    /// of returning immediately and not sending any packets.)
+    ///
+    /// Returns `None` if no route could be found.
+    pub fn estimate_ping_time_between(
+        &self,
+        location1: &NetworkPingLocation,
+        location2: &NetworkPingLocation,
+    ) -> Option<u32> {
+        let ping = unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_EstimatePingTimeBetweenTwoLocations(
+                self.utils,
+                &location1.inner,
+                &location2.inner,
+            )
+        };
+        if ping < 0 {
+            None
+        } else {
+            Some(ping as u32)
+        }
+    }
+
+    /// Same as `estimate_ping_time_between`, but assumes that one location is the local host.
+    /// This is a bit faster, especially if you need to calculate a bunch of
+    /// these in a loop to find the fastest one.
+    ///
+    /// Returns `None` if no route could be found or the local ping location isn't known yet.
+    pub fn estimate_ping_time_from_local_host(
+        &self,
+        remote_location: &NetworkPingLocation,
+    ) -> Option<u32> {
+        let ping = unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_EstimatePingTimeFromLocalHost(
+                self.utils,
+                &remote_location.inner,
+            )
+        };
+        if ping < 0 {
+            None
+        } else {
+            Some(ping as u32)
+        }
+    }
+
+    /// Convert a ping location into a text format suitable for sending over the wire.
+    /// The format is a compact and human readable.  However, it is subject to change
+    /// so please do not parse it yourself.
+    pub fn ping_location_to_string(&self, location: &NetworkPingLocation) -> String {
+        let mut buffer = vec![0u8; sys::k_cchMaxSteamNetworkingPingLocationString as usize];
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_ConvertPingLocationToString(
+                self.utils,
+                &location.inner,
+                buffer.as_mut_ptr() as _,
+                buffer.len() as _,
+            );
+            CStr::from_ptr(buffer.as_ptr() as _)
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
+    /// Parse back a ping location string created by `ping_location_to_string`.
+    ///
+    /// Returns `None` if the string couldn't be parsed.
+    pub fn parse_ping_location(&self, location: &str) -> Option<NetworkPingLocation> {
+        let location = CString::new(location).ok()?;
+        unsafe {
+            let mut result = NetworkPingLocation {
+                inner: std::mem::zeroed(),
+            };
+            if sys::SteamAPI_ISteamNetworkingUtils_ParsePingLocationString(
+                self.utils,
+                location.as_ptr(),
+                &mut result.inner,
+            ) {
+                Some(result)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Check if the ping data of sufficient recency is available, and if
+    /// it's too old, start refreshing it.
+    ///
+    /// Please only call this function when you *really* do need to force an
+    /// immediate refresh of the data.  (For example, in response to a specific
+    /// user input to refresh this information.)  Don't call it "just in case",
+    /// before every connection, etc.  That will cause extra traffic to be sent
+    /// for no benefit. The library will automatically refresh the information
+    /// as needed.
+    ///
+    /// Returns true if sufficiently recent data is already available.
+    pub fn check_ping_data_up_to_date(&self, max_age_seconds: f32) -> bool {
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_CheckPingDataUpToDate(self.utils, max_age_seconds)
+        }
+    }
+
+    /// Fetch ping time of best available relayed route from this host to
+    /// the specified data center.
+    ///
+    /// Returns the ping time in milliseconds and the POP that the route goes
+    /// through, or `None` if no route is available.
+    pub fn ping_to_data_center(&self, pop: NetworkingPopId) -> Option<(u32, NetworkingPopId)> {
+        let mut via_relay = 0;
+        let ping = unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_GetPingToDataCenter(
+                self.utils,
+                pop.0,
+                &mut via_relay,
+            )
+        };
+        if ping < 0 {
+            None
+        } else {
+            Some((ping as u32, NetworkingPopId(via_relay)))
+        }
+    }
+
+    /// Get *direct* ping time to the relays at the point of presence.
+    ///
+    /// Returns `None` if the ping time isn't known.
+    pub fn direct_ping_to_pop(&self, pop: NetworkingPopId) -> Option<u32> {
+        let ping =
+            unsafe { sys::SteamAPI_ISteamNetworkingUtils_GetDirectPingToPOP(self.utils, pop.0) };
+        if ping < 0 {
+            None
+        } else {
+            Some(ping as u32)
+        }
+    }
+
+    /// Get number of network points of presence in the config
+    pub fn pop_count(&self) -> usize {
+        unsafe { sys::SteamAPI_ISteamNetworkingUtils_GetPOPCount(self.utils) as usize }
+    }
+
+    /// Get the list of all network points of presence in the config
+    pub fn pop_list(&self) -> Vec<NetworkingPopId> {
+        let mut list = vec![0; self.pop_count()];
+        unsafe {
+            let count = sys::SteamAPI_ISteamNetworkingUtils_GetPOPList(
+                self.utils,
+                list.as_mut_ptr(),
+                list.len() as _,
+            );
+            list.truncate(count.max(0) as usize);
+        }
+        list.into_iter().map(NetworkingPopId).collect()
+    }
+
+    /// Set a global configuration value, i.e. the default for all interfaces,
+    /// listen sockets and connections created afterwards.
+    ///
+    /// Useful to simulate bad network conditions with e.g. `FakePacketLagSend`
+    /// or to change timeouts. Returns false if the value couldn't be set.
+    pub fn set_global_config_value_i32(&self, value: NetworkingConfigValue, val: i32) -> bool {
+        debug_assert_eq!(value.data_type(), NetworkingConfigDataType::Int32);
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_SetGlobalConfigValueInt32(
+                self.utils,
+                value.into(),
+                val,
+            )
+        }
+    }
+
+    /// Set a global float configuration value, e.g. `FakePacketLossSend`.
+    ///
+    /// Returns false if the value couldn't be set.
+    pub fn set_global_config_value_f32(&self, value: NetworkingConfigValue, val: f32) -> bool {
+        debug_assert_eq!(value.data_type(), NetworkingConfigDataType::Float);
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_SetGlobalConfigValueFloat(
+                self.utils,
+                value.into(),
+                val,
+            )
+        }
+    }
+
+    /// Set a global int64 configuration value.
+    ///
+    /// Returns false if the value couldn't be set.
+    pub fn set_global_config_value_i64(&self, value: NetworkingConfigValue, val: i64) -> bool {
+        debug_assert_eq!(value.data_type(), NetworkingConfigDataType::Int64);
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_SetConfigValue(
+                self.utils,
+                value.into(),
+                sys::ESteamNetworkingConfigScope::k_ESteamNetworkingConfig_Global,
+                0,
+                NetworkingConfigDataType::Int64.into(),
+                &val as *const i64 as *const c_void,
+            )
+        }
+    }
+
+    /// Set a global string configuration value, e.g. `SDRClientForceRelayCluster`.
+    ///
+    /// Returns false if the value couldn't be set, e.g. because it contains a nul byte.
+    pub fn set_global_config_value_string(&self, value: NetworkingConfigValue, val: &str) -> bool {
+        debug_assert_eq!(value.data_type(), NetworkingConfigDataType::String);
+        let val = match CString::new(val) {
+            Ok(val) => val,
+            Err(_) => return false,
+        };
+        unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_SetGlobalConfigValueString(
+                self.utils,
+                value.into(),
+                val.as_ptr(),
+            )
+        }
+    }
+
+    /// Get the global value of an int32 configuration value.
+    ///
+    /// Returns `None` if the value doesn't exist or isn't an int32.
+    pub fn global_config_value_i32(&self, value: NetworkingConfigValue) -> Option<i32> {
+        let mut buffer = [0u8; 4];
+        self.global_config_value(value, NetworkingConfigDataType::Int32, &mut buffer)
+            .ok()?;
+        Some(i32::from_ne_bytes(buffer))
+    }
+
+    /// Get the global value of an int64 configuration value.
+    ///
+    /// Returns `None` if the value doesn't exist or isn't an int64.
+    pub fn global_config_value_i64(&self, value: NetworkingConfigValue) -> Option<i64> {
+        let mut buffer = [0u8; 8];
+        self.global_config_value(value, NetworkingConfigDataType::Int64, &mut buffer)
+            .ok()?;
+        Some(i64::from_ne_bytes(buffer))
+    }
+
+    /// Get the global value of a float configuration value.
+    ///
+    /// Returns `None` if the value doesn't exist or isn't a float.
+    pub fn global_config_value_f32(&self, value: NetworkingConfigValue) -> Option<f32> {
+        let mut buffer = [0u8; 4];
+        self.global_config_value(value, NetworkingConfigDataType::Float, &mut buffer)
+            .ok()?;
+        Some(f32::from_ne_bytes(buffer))
+    }
+
+    /// Get the global value of a string configuration value.
+    ///
+    /// Returns `None` if the value doesn't exist or isn't a string.
+    pub fn global_config_value_string(&self, value: NetworkingConfigValue) -> Option<String> {
+        let mut buffer = vec![0u8; 256];
+        let len = loop {
+            match self.global_config_value(value, NetworkingConfigDataType::String, &mut buffer) {
+                Ok(len) => break len,
+                Err(Some(needed)) => buffer.resize(needed, 0),
+                Err(None) => return None,
+            }
+        };
+        buffer.truncate(len);
+        let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+        buffer.truncate(len);
+        Some(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    /// Reads a global configuration value into `buffer` and returns the size of the value.
+    ///
+    /// On failure the needed buffer size is returned if the buffer was too small.
+    fn global_config_value(
+        &self,
+        value: NetworkingConfigValue,
+        data_type: NetworkingConfigDataType,
+        buffer: &mut [u8],
+    ) -> Result<usize, Option<usize>> {
+        let mut out_data_type = data_type.into();
+        let mut size = buffer.len() as _;
+        let result = unsafe {
+            sys::SteamAPI_ISteamNetworkingUtils_GetConfigValue(
+                self.utils,
+                value.into(),
+                sys::ESteamNetworkingConfigScope::k_ESteamNetworkingConfig_Global,
+                0,
+                &mut out_data_type,
+                buffer.as_mut_ptr() as *mut c_void,
+                &mut size,
+            )
+        };
+        match result {
+            sys::ESteamNetworkingGetConfigValueResult::k_ESteamNetworkingGetConfigValue_OK
+            | sys::ESteamNetworkingGetConfigValueResult::k_ESteamNetworkingGetConfigValue_OKInherited
+                if out_data_type == data_type.into() =>
+            {
+                Ok(size as usize)
+            }
+            sys::ESteamNetworkingGetConfigValueResult::k_ESteamNetworkingGetConfigValue_BufferTooSmall => {
+                Err(Some(size as usize))
+            }
+            _ => Err(None),
+        }
+    }
+
     /// Register the callback for relay network status updates.
     ///
     /// Calling this more than once replaces the previous callback.
@@ -112,6 +455,43 @@ impl<Manager> NetworkingUtils<Manager> {
     }
 }
 
+/// Describes the location of a host in the relay network, relative to the data centers.
+///
+/// Use `NetworkingUtils::ping_location_to_string` to send it to other peers and
+/// `NetworkingUtils::estimate_ping_time_between` to compare locations without
+/// having to connect first.
+#[derive(Clone, Copy)]
+pub struct NetworkPingLocation {
+    inner: sys::SteamNetworkPingLocation_t,
+}
+
+/// Identifies a point of presence (a data center) of the relay network.
+///
+/// The id is a short code packed into an integer, e.g. `"iad"` or `"fra"`,
+/// which is what the `Display` implementation returns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkingPopId(pub u32);
+
+impl std::fmt::Display for NetworkingPopId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Same layout as `GetSteamNetworkingLocationPOPStringFromID`: the first three
+        // characters are stored big-endian in the low 24 bits, an optional fourth one
+        // in the high byte.
+        let id = self.0;
+        let bytes = [
+            (id >> 16) as u8,
+            (id >> 8) as u8,
+            id as u8,
+            (id >> 24) as u8,
+        ];
+        for byte in bytes.iter().take_while(|&&b| b != 0) {
+            write!(f, "{}", *byte as char)?;
+        }
+        Ok(())
+    }
+}
+
 pub struct RelayNetworkStatus {
     availability: NetworkingAvailabilityResult,
     is_ping_measurement_in_progress: bool,
@@ -195,9 +575,29 @@ unsafe impl Callback for RelayNetworkStatusCallback {
 
 #[cfg(test)]
 mod tests {
+    use super::NetworkingPopId;
     use crate::Client;
     use std::time::Duration;
 
+    /// Mirrors `CalculateSteamNetworkingPOPIDFromString` from the SDK headers
+    fn pop_id_from_str(code: &str) -> NetworkingPopId {
+        let c: Vec<u32> = code
+            .bytes()
+            .map(u32::from)
+            .chain(std::iter::repeat(0))
+            .take(4)
+            .collect();
+        NetworkingPopId(c[0] << 16 | c[1] << 8 | c[2] | c[3] << 24)
+    }
+
+    #[test]
+    fn test_pop_id_display() {
+        assert_eq!(pop_id_from_str("iad").0, 0x0069_6164);
+        assert_eq!(pop_id_from_str("iad").to_string(), "iad");
+        assert_eq!(pop_id_from_str("sto2").0, 0x3273_746f);
+        assert_eq!(pop_id_from_str("sto2").to_string(), "sto2");
+    }
+
     #[test]
     fn test_get_networking_status() {
         let (client, single) = Client::init().unwrap();
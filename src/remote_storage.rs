@@ -2,6 +2,8 @@ use super::*;
 #[cfg(test)]
 use serial_test_derive::serial;
 
+const CALLBACK_BASE_ID: i32 = 1300;
+
 /// Access to the steam remote storage interface
 pub struct RemoteStorage<Manager> {
     pub(crate) rs: *mut sys::ISteamRemoteStorage,
@@ -52,6 +54,9 @@ impl<Manager> Clone for RemoteStorage<Manager> {
 
 impl<Manager> RemoteStorage<Manager> {
     /// Toggles whether the steam cloud is enabled for the application
+    ///
+    /// This is the same setting the user can change in the properties of the game,
+    /// it should only be called in response to the user toggling an in-game option.
     pub fn set_cloud_enabled_for_app(&self, enabled: bool) {
         unsafe {
             sys::SteamAPI_ISteamRemoteStorage_SetCloudEnabledForApp(self.rs, enabled);
@@ -76,6 +81,24 @@ impl<Manager> RemoteStorage<Manager> {
         unsafe { sys::SteamAPI_ISteamRemoteStorage_IsCloudEnabledForAccount(self.rs) }
     }
 
+    /// Returns the total and the available number of bytes in the cloud storage
+    /// of the current user
+    pub fn quota(&self) -> (u64, u64) {
+        unsafe {
+            let mut total = 0;
+            let mut available = 0;
+            sys::SteamAPI_ISteamRemoteStorage_GetQuota(self.rs, &mut total, &mut available);
+            (total, available)
+        }
+    }
+
+    /// Returns whether files will actually be synced with the steam cloud
+    ///
+    /// This is the case when the cloud is enabled for both the account and the application.
+    pub fn is_cloud_enabled(&self) -> bool {
+        self.is_cloud_enabled_for_account() && self.is_cloud_enabled_for_app()
+    }
+
     /// Returns information about all files in the cloud storage
     pub fn files(&self) -> Vec<SteamFileInfo> {
         unsafe {
@@ -99,6 +122,241 @@ impl<Manager> RemoteStorage<Manager> {
         }
     }
 
+    /// Iterates over all files in the cloud storage
+    ///
+    /// Unlike `files` this also gives access to the files themselves.
+    pub fn cloud_files(&self) -> impl Iterator<Item = CloudFile<Manager>> + '_ {
+        let count = unsafe { sys::SteamAPI_ISteamRemoteStorage_GetFileCount(self.rs) };
+        (0..count.max(0)).map(move |idx| unsafe {
+            let mut size = 0;
+            let name = CStr::from_ptr(sys::SteamAPI_ISteamRemoteStorage_GetFileNameAndSize(
+                self.rs, idx, &mut size,
+            ));
+            CloudFile {
+                file: SteamFile {
+                    rs: self.rs,
+                    util: self.util,
+                    _inner: self.inner.clone(),
+                    name: name.to_owned(),
+                },
+                size: size as u64,
+            }
+        })
+    }
+
+    /// Shares a file from the cloud storage so that other users can download it
+    /// with `download_ugc`
+    pub fn share_file<F>(&self, name: &str, cb: F)
+    where
+        F: FnOnce(Result<UGCHandle, SteamError>) + 'static + Send,
+    {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamRemoteStorage_FileShare(self.rs, name.as_ptr());
+            register_call_result::<sys::RemoteStorageFileShareResult_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 7,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(UGCHandle(v.m_hFile))
+                    })
+                },
+            );
+        }
+    }
+
+    /// Downloads a file shared by another user, e.g. a screenshot or legacy workshop content
+    ///
+    /// Downloads with a lower `priority` are started first, 0 downloads immediately.
+    /// Once finished the content can be read with `read_ugc`.
+    pub fn download_ugc<F>(&self, handle: UGCHandle, priority: u32, cb: F)
+    where
+        F: FnOnce(Result<UGCDetails, SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call =
+                sys::SteamAPI_ISteamRemoteStorage_UGCDownload(self.rs, handle.0, priority);
+            register_call_result::<sys::RemoteStorageDownloadUGCResult_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 17,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(UGCDetails {
+                            app_id: AppId(v.m_nAppID),
+                            name: CStr::from_ptr(v.m_pchFileName.as_ptr())
+                                .to_string_lossy()
+                                .into_owned(),
+                            size: v.m_nSizeInBytes as u32,
+                            owner: SteamId(v.m_ulSteamIDOwner),
+                        })
+                    })
+                },
+            );
+        }
+    }
+
+    /// Returns the number of bytes downloaded and expected of shared content
+    ///
+    /// Returns `None` if the content isn't being downloaded.
+    pub fn ugc_download_progress(&self, handle: UGCHandle) -> Option<(u32, u32)> {
+        unsafe {
+            let mut downloaded = 0;
+            let mut expected = 0;
+            if sys::SteamAPI_ISteamRemoteStorage_GetUGCDownloadProgress(
+                self.rs,
+                handle.0,
+                &mut downloaded,
+                &mut expected,
+            ) {
+                Some((downloaded as u32, expected as u32))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the details of downloaded shared content
+    ///
+    /// Returns `None` if the content hasn't been downloaded yet.
+    pub fn ugc_details(&self, handle: UGCHandle) -> Option<UGCDetails> {
+        unsafe {
+            let mut app_id = 0;
+            let mut name = std::ptr::null_mut();
+            let mut size = 0;
+            let mut owner: sys::CSteamID = std::mem::zeroed();
+            if sys::SteamAPI_ISteamRemoteStorage_GetUGCDetails(
+                self.rs,
+                handle.0,
+                &mut app_id,
+                &mut name,
+                &mut size,
+                &mut owner,
+            ) {
+                Some(UGCDetails {
+                    app_id: AppId(app_id),
+                    name: if name.is_null() {
+                        String::new()
+                    } else {
+                        CStr::from_ptr(name).to_string_lossy().into_owned()
+                    },
+                    size: size as u32,
+                    owner: SteamId(owner.m_steamid.m_unAll64Bits),
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Reads downloaded shared content into `buf`, starting at `offset`
+    ///
+    /// Returns the number of bytes read.
+    pub fn read_ugc(
+        &self,
+        handle: UGCHandle,
+        offset: u32,
+        buf: &mut [u8],
+        action: UGCReadAction,
+    ) -> usize {
+        unsafe {
+            sys::SteamAPI_ISteamRemoteStorage_UGCRead(
+                self.rs,
+                handle.0,
+                buf.as_mut_ptr() as *mut _,
+                buf.len() as _,
+                offset,
+                action.into(),
+            )
+            .max(0) as usize
+        }
+    }
+
+    /// Returns the files that were changed by steam while the game was running
+    ///
+    /// This should be called after receiving the `LocalFileChange` callback.
+    pub fn local_file_changes(&self) -> Vec<LocalFileChangeInfo> {
+        unsafe {
+            let count = sys::SteamAPI_ISteamRemoteStorage_GetLocalFileChangeCount(self.rs);
+            let mut changes = Vec::with_capacity(count.max(0) as usize);
+            for idx in 0..count {
+                let mut change_type =
+                    sys::ERemoteStorageLocalFileChange::k_ERemoteStorageLocalFileChange_Invalid;
+                let mut path_type =
+                    sys::ERemoteStorageFilePathType::k_ERemoteStorageFilePathType_Invalid;
+                let name = sys::SteamAPI_ISteamRemoteStorage_GetLocalFileChange(
+                    self.rs,
+                    idx,
+                    &mut change_type,
+                    &mut path_type,
+                );
+                let change_type = match change_type {
+                    sys::ERemoteStorageLocalFileChange::k_ERemoteStorageLocalFileChange_FileUpdated => {
+                        LocalFileChangeType::Updated
+                    }
+                    sys::ERemoteStorageLocalFileChange::k_ERemoteStorageLocalFileChange_FileDeleted => {
+                        LocalFileChangeType::Deleted
+                    }
+                    _ => continue,
+                };
+                if name.is_null() {
+                    continue;
+                }
+                changes.push(LocalFileChangeInfo {
+                    name: CStr::from_ptr(name).to_string_lossy().into_owned(),
+                    change_type,
+                    absolute_path: path_type
+                        == sys::ERemoteStorageFilePathType::k_ERemoteStorageFilePathType_Absolute,
+                });
+            }
+            changes
+        }
+    }
+
+    /// Starts grouping file writes and deletes into a single batch
+    ///
+    /// Steam only syncs the batch with the cloud once it's complete, so a crash
+    /// in the middle of saving several files doesn't leave an inconsistent save.
+    /// The batch ends when the returned guard is dropped.
+    ///
+    /// Returns `None` if a batch is already in progress.
+    pub fn begin_file_write_batch(&self) -> Option<FileWriteBatch<Manager>> {
+        unsafe {
+            if sys::SteamAPI_ISteamRemoteStorage_BeginFileWriteBatch(self.rs) {
+                Some(FileWriteBatch {
+                    rs: self.rs,
+                    _inner: self.inner.clone(),
+                    ended: false,
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns a helper that saves and loads a value as the given cloud file
+    ///
+    /// `version` is stored alongside the value, see `CloudSaves` for details.
+    #[cfg(feature = "cloud-saves")]
+    pub fn cloud_saves<T>(&self, name: &str, version: u32) -> CloudSaves<T, Manager> {
+        CloudSaves {
+            storage: self.clone(),
+            name: name.to_owned(),
+            version,
+            compressed: false,
+            _value: std::marker::PhantomData,
+        }
+    }
+
     /// Returns a handle to a steam cloud file
     ///
     /// The file does not have to exist.
@@ -110,6 +368,172 @@ impl<Manager> RemoteStorage<Manager> {
             name: CString::new(name).unwrap(),
         }
     }
+
+    /// Writes the whole file to the steam cloud, replacing any previous contents
+    ///
+    /// This blocks until the file has been written to disk, the upload to the
+    /// cloud happens in the background.
+    pub fn write_file(&self, name: &str, data: &[u8]) -> std::io::Result<()> {
+        let name = CString::new(name).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+        let len = i32::try_from(data.len()).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+        unsafe {
+            if sys::SteamAPI_ISteamRemoteStorage_FileWrite(
+                self.rs,
+                name.as_ptr(),
+                data.as_ptr() as *const _,
+                len,
+            ) {
+                Ok(())
+            } else {
+                Err(std::io::ErrorKind::Other.into())
+            }
+        }
+    }
+
+    /// Reads the whole file from the steam cloud
+    pub fn read_file(&self, name: &str) -> std::io::Result<Vec<u8>> {
+        let name = CString::new(name).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+        unsafe {
+            if !sys::SteamAPI_ISteamRemoteStorage_FileExists(self.rs, name.as_ptr()) {
+                return Err(std::io::ErrorKind::NotFound.into());
+            }
+            let size = sys::SteamAPI_ISteamRemoteStorage_GetFileSize(self.rs, name.as_ptr());
+            let mut data = vec![0u8; size.max(0) as usize];
+            let read = sys::SteamAPI_ISteamRemoteStorage_FileRead(
+                self.rs,
+                name.as_ptr(),
+                data.as_mut_ptr() as *mut _,
+                size,
+            );
+            if read != size {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            Ok(data)
+        }
+    }
+
+    /// Writes the whole file to the steam cloud without blocking.
+    ///
+    /// Fails with `SteamError::InvalidParameter` straight away if the name or
+    /// data are invalid, e.g. if the name contains a nul byte.
+    pub fn write_file_async<F>(&self, name: &str, data: &[u8], cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        let name = match CString::new(name) {
+            Ok(name) => name,
+            Err(_) => return cb(Err(SteamError::InvalidParameter)),
+        };
+        let len = match u32::try_from(data.len()) {
+            Ok(len) => len,
+            Err(_) => return cb(Err(SteamError::InvalidParameter)),
+        };
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamRemoteStorage_FileWriteAsync(
+                self.rs,
+                name.as_ptr(),
+                data.as_ptr() as *const _,
+                len,
+            );
+            if api_call == sys::k_uAPICallInvalid {
+                return cb(Err(SteamError::InvalidParameter));
+            }
+            register_call_result::<sys::RemoteStorageFileWriteAsyncComplete_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 31,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Reads the whole file from the steam cloud without blocking.
+    ///
+    /// Fails with `SteamError::FileNotFound` straight away if the file doesn't
+    /// exist and with `SteamError::InvalidParameter` if the name contains a nul
+    /// byte.
+    pub fn read_file_async<F>(&self, name: &str, cb: F)
+    where
+        F: FnOnce(Result<Vec<u8>, SteamError>) + 'static + Send,
+    {
+        let name = match CString::new(name) {
+            Ok(name) => name,
+            Err(_) => return cb(Err(SteamError::InvalidParameter)),
+        };
+        unsafe {
+            if !sys::SteamAPI_ISteamRemoteStorage_FileExists(self.rs, name.as_ptr()) {
+                return cb(Err(SteamError::FileNotFound));
+            }
+            let size = sys::SteamAPI_ISteamRemoteStorage_GetFileSize(self.rs, name.as_ptr());
+            if size <= 0 {
+                // Steam doesn't start an async read for empty files
+                return cb(Ok(Vec::new()));
+            }
+            let api_call = sys::SteamAPI_ISteamRemoteStorage_FileReadAsync(
+                self.rs,
+                name.as_ptr(),
+                0,
+                size as _,
+            );
+            if api_call == sys::k_uAPICallInvalid {
+                return cb(Err(SteamError::FileNotFound));
+            }
+            register_call_result::<sys::RemoteStorageFileReadAsyncComplete_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 32,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        // The data has to be fetched while the call result is being handled
+                        let rs = sys::SteamAPI_SteamRemoteStorage_v016();
+                        let mut data = vec![0u8; v.m_cubRead as usize];
+                        if sys::SteamAPI_ISteamRemoteStorage_FileReadAsyncComplete(
+                            rs,
+                            v.m_hFileReadAsync,
+                            data.as_mut_ptr() as *mut _,
+                            v.m_cubRead,
+                        ) {
+                            Ok(data)
+                        } else {
+                            Err(SteamError::IOFailure)
+                        }
+                    })
+                },
+            );
+        }
+    }
+
+    /// Like `write_file_async`, but returns a future instead of taking a callback
+    #[cfg(feature = "futures")]
+    pub fn write_file_future(
+        &self,
+        name: &str,
+        data: &[u8],
+    ) -> CallResultFuture<Result<(), SteamError>> {
+        let (future, complete) = CallResultFuture::new();
+        self.write_file_async(name, data, complete);
+        future
+    }
+
+    /// Like `read_file_async`, but returns a future instead of taking a callback
+    #[cfg(feature = "futures")]
+    pub fn read_file_future(&self, name: &str) -> CallResultFuture<Result<Vec<u8>, SteamError>> {
+        let (future, complete) = CallResultFuture::new();
+        self.read_file_async(name, complete);
+        future
+    }
 }
 
 /// A handle for a possible steam cloud file
@@ -127,8 +551,11 @@ impl<Manager> SteamFile<Manager> {
     pub fn delete(&self) -> bool {
         unsafe { sys::SteamAPI_ISteamRemoteStorage_FileDelete(self.rs, self.name.as_ptr()) }
     }
+
     /// Deletes the file remotely whilst keeping it locally.
     ///
+    /// This frees up cloud quota, the file will no longer be synced to other machines.
+    ///
     /// Returns whether a file was actually forgotten
     pub fn forget(&self) -> bool {
         unsafe { sys::SteamAPI_ISteamRemoteStorage_FileForget(self.rs, self.name.as_ptr()) }
@@ -144,11 +571,14 @@ impl<Manager> SteamFile<Manager> {
         unsafe { sys::SteamAPI_ISteamRemoteStorage_FilePersisted(self.rs, self.name.as_ptr()) }
     }
 
-    // Returns the timestamp of the file
+    /// Returns the time the file was last modified, in unix epoch seconds
     pub fn timestamp(&self) -> i64 {
         unsafe { sys::SteamAPI_ISteamRemoteStorage_GetFileTimestamp(self.rs, self.name.as_ptr()) }
     }
 
+    /// Opens the file for streaming writes, replacing any previous contents
+    ///
+    /// The file is committed when the writer is dropped.
     pub fn write(self) -> SteamFileWriter<Manager> {
         unsafe {
             let handle =
@@ -157,6 +587,7 @@ impl<Manager> SteamFile<Manager> {
         }
     }
 
+    /// Opens the file for reading
     pub fn read(self) -> SteamFileReader<Manager> {
         unsafe {
             SteamFileReader {
@@ -168,6 +599,87 @@ impl<Manager> SteamFile<Manager> {
         }
     }
 }
+
+/// A batch of file writes, see `RemoteStorage::begin_file_write_batch`
+///
+/// Ends the batch when dropped
+pub struct FileWriteBatch<Manager> {
+    rs: *mut sys::ISteamRemoteStorage,
+    _inner: Arc<Inner<Manager>>,
+    ended: bool,
+}
+
+impl<Manager> FileWriteBatch<Manager> {
+    /// Ends the batch, returning whether it was ended successfully
+    pub fn end(mut self) -> bool {
+        self.ended = true;
+        unsafe { sys::SteamAPI_ISteamRemoteStorage_EndFileWriteBatch(self.rs) }
+    }
+}
+
+impl<Manager> Drop for FileWriteBatch<Manager> {
+    fn drop(&mut self) {
+        if !self.ended {
+            unsafe {
+                sys::SteamAPI_ISteamRemoteStorage_EndFileWriteBatch(self.rs);
+            }
+        }
+    }
+}
+
+/// A file in the steam cloud, see `RemoteStorage::cloud_files`
+pub struct CloudFile<Manager> {
+    file: SteamFile<Manager>,
+    size: u64,
+}
+
+impl<Manager> CloudFile<Manager> {
+    /// The name of the file
+    pub fn name(&self) -> String {
+        self.file.name.to_string_lossy().into_owned()
+    }
+
+    /// The size of the file in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the timestamp of the file
+    pub fn timestamp(&self) -> i64 {
+        self.file.timestamp()
+    }
+
+    /// Returns whether the file is persisted in the steam cloud
+    pub fn is_persisted(&self) -> bool {
+        self.file.is_persisted()
+    }
+
+    /// Reads the whole file
+    pub fn read_all(&self) -> std::io::Result<Vec<u8>> {
+        use std::io::Read;
+        let mut data = Vec::with_capacity(self.size as usize);
+        self.handle().read().read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    /// Deletes the file locally and remotely.
+    ///
+    /// Returns whether a file was actually deleted
+    pub fn delete(&self) -> bool {
+        self.file.delete()
+    }
+
+    /// Returns a handle to the file
+    pub fn handle(&self) -> SteamFile<Manager> {
+        SteamFile {
+            rs: self.file.rs,
+            util: self.file.util,
+            _inner: self.file._inner.clone(),
+            name: self.file.name.clone(),
+        }
+    }
+}
+
 /// A write handle for a steam cloud file
 pub struct SteamFileWriter<Manager> {
     file: SteamFile<Manager>,
@@ -289,6 +801,87 @@ impl<Manager> std::io::Seek for SteamFileReader<Manager> {
     }
 }
 
+/// Called when steam changed local files while the game was running, e.g. after
+/// syncing the cloud when resuming a suspended Steam Deck.
+///
+/// Use `RemoteStorage::local_file_changes` to find out which files changed.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocalFileChange;
+
+unsafe impl Callback for LocalFileChange {
+    const ID: i32 = CALLBACK_BASE_ID + 33;
+    const SIZE: i32 = ::std::mem::size_of::<sys::RemoteStorageLocalFileChange_t>() as i32;
+
+    unsafe fn from_raw(_: *mut c_void) -> Self {
+        LocalFileChange
+    }
+}
+
+/// How a local file was changed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LocalFileChangeType {
+    /// The file was created or its contents were replaced
+    Updated,
+    /// The file was deleted
+    Deleted,
+}
+
+/// A local file steam changed, see `RemoteStorage::local_file_changes`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocalFileChangeInfo {
+    /// The name of the file
+    pub name: String,
+    /// How the file was changed
+    pub change_type: LocalFileChangeType,
+    /// Whether `name` is an absolute path instead of a name usable with `RemoteStorage::file`
+    pub absolute_path: bool,
+}
+
+/// A handle to a file shared through the steam cloud by any user
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UGCHandle(pub u64);
+
+/// Information about downloaded shared content
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UGCDetails {
+    /// The app the content belongs to
+    pub app_id: AppId,
+    /// The name of the shared file
+    pub name: String,
+    /// The size of the content in bytes
+    pub size: u32,
+    /// The user that shared the content
+    pub owner: SteamId,
+}
+
+/// What to do after reading shared content, see `RemoteStorage::read_ugc`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UGCReadAction {
+    /// Keeps the file open until all of it has been read, then closes it
+    ContinueReadingUntilFinished,
+    /// Keeps the file open even when all of it has been read
+    ContinueReading,
+    /// Closes the file after this read
+    Close,
+}
+
+impl From<UGCReadAction> for sys::EUGCReadAction {
+    fn from(action: UGCReadAction) -> Self {
+        match action {
+            UGCReadAction::ContinueReadingUntilFinished => {
+                sys::EUGCReadAction::k_EUGCRead_ContinueReadingUntilFinished
+            }
+            UGCReadAction::ContinueReading => sys::EUGCReadAction::k_EUGCRead_ContinueReading,
+            UGCReadAction::Close => sys::EUGCReadAction::k_EUGCRead_Close,
+        }
+    }
+}
+
 /// Name and size information about a file in the steam cloud
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -299,6 +892,173 @@ pub struct SteamFileInfo {
     pub size: u64,
 }
 
+/// Saves a serializable value as a single steam cloud file
+///
+/// The value is stored as JSON behind a small header containing the version passed
+/// to `RemoteStorage::cloud_saves`. Loading a save written by a newer version of the
+/// game fails with `CloudSaveError::UnsupportedVersion`, older saves are deserialized
+/// as the current type so new fields should have serde defaults.
+///
+/// Saves can be compressed with `compressed`, the header records whether a save is
+/// compressed so saves written either way can always be loaded.
+///
+/// Requires the `cloud-saves` feature.
+#[cfg(feature = "cloud-saves")]
+pub struct CloudSaves<T, Manager> {
+    storage: RemoteStorage<Manager>,
+    name: String,
+    version: u32,
+    compressed: bool,
+    _value: std::marker::PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "cloud-saves")]
+const CLOUD_SAVE_MAGIC: &[u8; 4] = b"SWCS";
+
+/// Set in the flags of the header if the payload is deflate compressed
+#[cfg(feature = "cloud-saves")]
+const CLOUD_SAVE_COMPRESSED: u8 = 1;
+
+#[cfg(feature = "cloud-saves")]
+impl<T, Manager> CloudSaves<T, Manager>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    /// Sets whether saves are compressed, off by default.
+    ///
+    /// Worth it for large saves since the steam cloud quota is per user.
+    pub fn compressed(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
+    }
+
+    /// Serializes the value and writes it to the cloud file
+    pub fn save(&self, value: &T) -> Result<(), CloudSaveError> {
+        let data = encode_cloud_save(self.version, self.compressed, value)?;
+        self.storage.write_file(&self.name, &data)?;
+        Ok(())
+    }
+
+    /// Reads the cloud file and deserializes the value
+    ///
+    /// Returns `None` if nothing was saved yet.
+    pub fn load(&self) -> Result<Option<T>, CloudSaveError> {
+        match self.storage.read_file(&self.name) {
+            Ok(data) => decode_cloud_save(self.version, &data).map(|(_, value)| Some(value)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Returns the version the cloud file was saved with
+    ///
+    /// Returns `None` if nothing was saved yet.
+    pub fn saved_version(&self) -> Result<Option<u32>, CloudSaveError> {
+        match self.storage.read_file(&self.name) {
+            Ok(data) => decode_cloud_save_header(&data).map(|(version, _, _)| Some(version)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Deletes the cloud file
+    ///
+    /// Returns whether a file was actually deleted
+    pub fn delete(&self) -> bool {
+        self.storage.file(&self.name).delete()
+    }
+}
+
+#[cfg(feature = "cloud-saves")]
+fn encode_cloud_save<T: Serialize>(
+    version: u32,
+    compressed: bool,
+    value: &T,
+) -> Result<Vec<u8>, CloudSaveError> {
+    let mut data = Vec::new();
+    data.extend_from_slice(CLOUD_SAVE_MAGIC);
+    data.extend_from_slice(&version.to_le_bytes());
+    if compressed {
+        data.push(CLOUD_SAVE_COMPRESSED);
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(&mut data, flate2::Compression::default());
+        serde_json::to_writer(&mut encoder, value)?;
+        encoder.finish()?;
+    } else {
+        data.push(0);
+        serde_json::to_writer(&mut data, value)?;
+    }
+    Ok(data)
+}
+
+#[cfg(feature = "cloud-saves")]
+fn decode_cloud_save_header(data: &[u8]) -> Result<(u32, u8, &[u8]), CloudSaveError> {
+    if data.len() < 9 || &data[..4] != CLOUD_SAVE_MAGIC {
+        return Err(CloudSaveError::InvalidHeader);
+    }
+    let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    Ok((version, data[8], &data[9..]))
+}
+
+#[cfg(feature = "cloud-saves")]
+fn decode_cloud_save<T: serde::de::DeserializeOwned>(
+    current_version: u32,
+    data: &[u8],
+) -> Result<(u32, T), CloudSaveError> {
+    let (version, flags, payload) = decode_cloud_save_header(data)?;
+    if version > current_version {
+        return Err(CloudSaveError::UnsupportedVersion(version));
+    }
+    let value = if flags & CLOUD_SAVE_COMPRESSED != 0 {
+        serde_json::from_reader(flate2::read::DeflateDecoder::new(payload))?
+    } else {
+        serde_json::from_slice(payload)?
+    };
+    Ok((version, value))
+}
+
+/// Errors from `CloudSaves`
+#[cfg(feature = "cloud-saves")]
+#[derive(Debug, Error)]
+pub enum CloudSaveError {
+    /// The cloud file couldn't be read or written
+    #[error("cloud file access failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The value couldn't be serialized or deserialized
+    #[error("cloud save serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+    /// The cloud file wasn't written by `CloudSaves`
+    #[error("cloud file is not a cloud save")]
+    InvalidHeader,
+    /// The save was written by a newer version
+    #[error("cloud save version {0} is newer than the supported version")]
+    UnsupportedVersion(u32),
+}
+
+#[cfg(feature = "cloud-saves")]
+#[test]
+fn test_cloud_save_encoding() {
+    let data = encode_cloud_save(2, false, &vec![1u32, 2, 3]).unwrap();
+    let (version, value): (u32, Vec<u32>) = decode_cloud_save(2, &data).unwrap();
+    assert_eq!(version, 2);
+    assert_eq!(value, vec![1, 2, 3]);
+
+    let large = vec![7u32; 1000];
+    let compressed = encode_cloud_save(2, true, &large).unwrap();
+    assert!(compressed.len() < encode_cloud_save(2, false, &large).unwrap().len());
+    let (_, value): (u32, Vec<u32>) = decode_cloud_save(2, &compressed).unwrap();
+    assert_eq!(value, large);
+
+    assert!(matches!(
+        decode_cloud_save::<Vec<u32>>(1, &data),
+        Err(CloudSaveError::UnsupportedVersion(2))
+    ));
+    assert!(matches!(
+        decode_cloud_save::<Vec<u32>>(2, b"[1,2,3]"),
+        Err(CloudSaveError::InvalidHeader)
+    ));
+}
+
 #[test]
 #[serial]
 fn test_cloud() {
@@ -327,5 +1087,15 @@ fn test_cloud() {
     test.read().read_to_string(&mut output).unwrap();
     println!("Got: {:?}", output);
 
+    for f in rs.cloud_files() {
+        println!("{} {} {}", f.name(), f.size(), f.timestamp());
+    }
+
     assert_eq!(output, "Testing");
+
+    let (total, available) = rs.quota();
+    assert!(available <= total);
+
+    rs.write_file("test2.txt", b"Whole file").unwrap();
+    assert_eq!(rs.read_file("test2.txt").unwrap(), b"Whole file");
 }
@@ -94,6 +94,8 @@ impl Server {
                     sockets: Default::default(),
                     independent_connections: Default::default(),
                     connection_callback: Default::default(),
+                    #[cfg(feature = "futures")]
+                    wakers: Default::default(),
                 }),
             });
             Ok((
@@ -310,6 +312,18 @@ impl Server {
         }
     }
 
+    /// Returns an accessor to the steam HTTP interface
+    pub fn http(&self) -> Http<ServerManager> {
+        unsafe {
+            let http = sys::SteamAPI_SteamGameServerHTTP_v003();
+            debug_assert!(!http.is_null());
+            Http {
+                http,
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
     /* TODO: Buggy currently?
     /// Returns an accessor to the steam apps interface
     pub fn apps(&self) -> Apps<ServerManager> {
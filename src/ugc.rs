@@ -2,13 +2,14 @@ use sys::DepotId_t;
 
 use super::*;
 
+use std::collections::{HashMap, HashSet};
 use std::error;
 use std::ffi::{CStr, CString};
 use std::fmt;
 use std::marker;
 use std::mem;
 use std::os::raw::c_char;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub const RESULTS_PER_PAGE: u32 = sys::kNumUGCResultsPerPage as u32;
 
@@ -189,12 +190,19 @@ impl AppIDs {
 /// Query result sorting
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserListOrder {
+    /// Oldest items first
     CreationOrderAsc,
+    /// Newest items first
     CreationOrderDesc,
+    /// Sorted alphabetically by title
     TitleAsc,
+    /// Most recently updated items first
     LastUpdatedDesc,
+    /// Most recently subscribed items first
     SubscriptionDateDesc,
+    /// Highest voted items first
     VoteScoreDesc,
+    /// Items reported for moderation first
     ForModeration,
 }
 
@@ -224,6 +232,88 @@ impl Into<sys::EUserUGCListSortOrder> for UserListOrder {
     }
 }
 
+/// Sort order and filtering of `UGC::query_all`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UGCQueryType {
+    RankedByVote,
+    RankedByPublicationDate,
+    AcceptedForGameRankedByAcceptanceDate,
+    RankedByTrend,
+    FavoritedByFriendsRankedByPublicationDate,
+    CreatedByFriendsRankedByPublicationDate,
+    RankedByNumTimesReported,
+    CreatedByFollowedUsersRankedByPublicationDate,
+    NotYetRated,
+    RankedByTotalVotesAsc,
+    RankedByVotesUp,
+    RankedByTextSearch,
+    RankedByTotalUniqueSubscriptions,
+    RankedByPlaytimeTrend,
+    RankedByTotalPlaytime,
+    RankedByAveragePlaytimeTrend,
+    RankedByLifetimeAveragePlaytime,
+    RankedByPlaytimeSessionsTrend,
+    RankedByLifetimePlaytimeSessions,
+    RankedByLastUpdatedDate,
+}
+
+impl From<UGCQueryType> for sys::EUGCQuery {
+    fn from(query_type: UGCQueryType) -> Self {
+        match query_type {
+            UGCQueryType::RankedByVote => sys::EUGCQuery::k_EUGCQuery_RankedByVote,
+            UGCQueryType::RankedByPublicationDate => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByPublicationDate
+            }
+            UGCQueryType::AcceptedForGameRankedByAcceptanceDate => {
+                sys::EUGCQuery::k_EUGCQuery_AcceptedForGameRankedByAcceptanceDate
+            }
+            UGCQueryType::RankedByTrend => sys::EUGCQuery::k_EUGCQuery_RankedByTrend,
+            UGCQueryType::FavoritedByFriendsRankedByPublicationDate => {
+                sys::EUGCQuery::k_EUGCQuery_FavoritedByFriendsRankedByPublicationDate
+            }
+            UGCQueryType::CreatedByFriendsRankedByPublicationDate => {
+                sys::EUGCQuery::k_EUGCQuery_CreatedByFriendsRankedByPublicationDate
+            }
+            UGCQueryType::RankedByNumTimesReported => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByNumTimesReported
+            }
+            UGCQueryType::CreatedByFollowedUsersRankedByPublicationDate => {
+                sys::EUGCQuery::k_EUGCQuery_CreatedByFollowedUsersRankedByPublicationDate
+            }
+            UGCQueryType::NotYetRated => sys::EUGCQuery::k_EUGCQuery_NotYetRated,
+            UGCQueryType::RankedByTotalVotesAsc => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByTotalVotesAsc
+            }
+            UGCQueryType::RankedByVotesUp => sys::EUGCQuery::k_EUGCQuery_RankedByVotesUp,
+            UGCQueryType::RankedByTextSearch => sys::EUGCQuery::k_EUGCQuery_RankedByTextSearch,
+            UGCQueryType::RankedByTotalUniqueSubscriptions => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByTotalUniqueSubscriptions
+            }
+            UGCQueryType::RankedByPlaytimeTrend => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByPlaytimeTrend
+            }
+            UGCQueryType::RankedByTotalPlaytime => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByTotalPlaytime
+            }
+            UGCQueryType::RankedByAveragePlaytimeTrend => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByAveragePlaytimeTrend
+            }
+            UGCQueryType::RankedByLifetimeAveragePlaytime => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByLifetimeAveragePlaytime
+            }
+            UGCQueryType::RankedByPlaytimeSessionsTrend => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByPlaytimeSessionsTrend
+            }
+            UGCQueryType::RankedByLifetimePlaytimeSessions => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByLifetimePlaytimeSessions
+            }
+            UGCQueryType::RankedByLastUpdatedDate => {
+                sys::EUGCQuery::k_EUGCQuery_RankedByLastUpdatedDate
+            }
+        }
+    }
+}
+
 /// Available user-specific lists.
 /// Certain ones are only available to the currently logged in user.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -333,18 +423,102 @@ impl Into<sys::EItemStatistic> for UGCStatisticType {
     }
 }
 
+/// The type of an additional preview of a workshop item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ItemPreviewType {
+    /// A standard image file, e.g. jpg, png or gif
+    Image,
+    /// A YouTube video id
+    YouTubeVideo,
+    /// A Sketchfab model id
+    Sketchfab,
+    /// A standard image file laid out as a horizontal cross environment map
+    EnvironmentMapHorizontalCross,
+    /// A standard image file laid out as a lat-long environment map
+    EnvironmentMapLatLong,
+    /// A preview type this version of the crate doesn't know about
+    Unknown,
+}
+impl From<sys::EItemPreviewType> for ItemPreviewType {
+    fn from(preview_type: sys::EItemPreviewType) -> Self {
+        match preview_type {
+            sys::EItemPreviewType::k_EItemPreviewType_Image => ItemPreviewType::Image,
+            sys::EItemPreviewType::k_EItemPreviewType_YouTubeVideo => ItemPreviewType::YouTubeVideo,
+            sys::EItemPreviewType::k_EItemPreviewType_Sketchfab => ItemPreviewType::Sketchfab,
+            sys::EItemPreviewType::k_EItemPreviewType_EnvironmentMap_HorizontalCross => {
+                ItemPreviewType::EnvironmentMapHorizontalCross
+            }
+            sys::EItemPreviewType::k_EItemPreviewType_EnvironmentMap_LatLong => {
+                ItemPreviewType::EnvironmentMapLatLong
+            }
+            _ => ItemPreviewType::Unknown,
+        }
+    }
+}
+
+/// The type of a preview file added with `UpdateHandle::add_preview_file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PreviewFileType {
+    /// A standard image file, e.g. jpg, png or gif
+    Image,
+    /// A Sketchfab model id
+    Sketchfab,
+    /// A standard image file laid out as a horizontal cross environment map
+    EnvironmentMapHorizontalCross,
+    /// A standard image file laid out as a lat-long environment map
+    EnvironmentMapLatLong,
+}
+impl From<PreviewFileType> for sys::EItemPreviewType {
+    fn from(preview_type: PreviewFileType) -> Self {
+        match preview_type {
+            PreviewFileType::Image => sys::EItemPreviewType::k_EItemPreviewType_Image,
+            PreviewFileType::Sketchfab => sys::EItemPreviewType::k_EItemPreviewType_Sketchfab,
+            PreviewFileType::EnvironmentMapHorizontalCross => {
+                sys::EItemPreviewType::k_EItemPreviewType_EnvironmentMap_HorizontalCross
+            }
+            PreviewFileType::EnvironmentMapLatLong => {
+                sys::EItemPreviewType::k_EItemPreviewType_EnvironmentMap_LatLong
+            }
+        }
+    }
+}
+
+/// An additional preview of a workshop item, see `QueryResults::additional_preview`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdditionalPreview {
+    /// The url of the preview, or the video/model id depending on the type
+    pub url_or_video_id: String,
+    /// The name of the file that was originally uploaded, if the preview is a file
+    pub original_file_name: String,
+    /// The type of the preview
+    pub preview_type: ItemPreviewType,
+}
+
 bitflags! {
+    /// The local state of a workshop item, see `UGC::item_state`
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct ItemState: u32 {
+        /// The item is not tracked on the client
         const NONE = 0;
+        /// The current user is subscribed to this item, not just cached
         const SUBSCRIBED = 1;
+        /// The item was created with the old workshop functions in ISteamRemoteStorage
         const LEGACY_ITEM = 2;
+        /// The item is installed and usable, but maybe out of date
         const INSTALLED = 4;
+        /// The item needs an update, either because it's not installed yet or the creator updated the content
         const NEEDS_UPDATE = 8;
+        /// The item update is currently downloading
         const DOWNLOADING = 16;
+        /// `UGC::download_item` was called for this item, the content isn't available until `DownloadItemResult` is posted
         const DOWNLOAD_PENDING = 32;
     }
 }
 
+/// Called when a workshop item has been downloaded, e.g. after calling `UGC::download_item`
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DownloadItemResult {
@@ -371,11 +545,47 @@ unsafe impl Callback for DownloadItemResult {
     }
 }
 
+/// How the current user voted on a workshop item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UserItemVote {
+    /// The user hasn't voted on the item
+    None,
+    Up,
+    Down,
+    /// The user chose to skip voting on the item
+    Skipped,
+}
+
+/// Called when a workshop item has been installed or updated while the game is running
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ItemInstalled {
+    pub app_id: AppId,
+    pub published_file_id: PublishedFileId,
+}
+
+unsafe impl Callback for ItemInstalled {
+    const ID: i32 = CALLBACK_BASE_ID + 5;
+    const SIZE: i32 = ::std::mem::size_of::<sys::ItemInstalled_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::ItemInstalled_t);
+        ItemInstalled {
+            app_id: AppId(val.m_unAppID),
+            published_file_id: PublishedFileId(val.m_nPublishedFileId),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InstallInfo {
-    pub folder: String,
+    /// The folder the content of the item is installed to
+    pub folder: PathBuf,
+    /// The size of the item on disk in bytes
     pub size_on_disk: u64,
+    /// The time the item was last updated, in unix epoch seconds format
     pub timestamp: u32,
 }
 
@@ -433,6 +643,9 @@ impl<Manager> UGC<Manager> {
     }
 
     /// Subscribes to a workshop item
+    ///
+    /// The item will be downloaded and installed as soon as possible, use `item_state`
+    /// to find out when it is ready.
     pub fn subscribe_item<F>(&self, published_file_id: PublishedFileId, cb: F)
     where
         F: FnOnce(Result<(), SteamError>) + 'static + Send,
@@ -456,6 +669,9 @@ impl<Manager> UGC<Manager> {
         }
     }
 
+    /// Unsubscribes from a workshop item
+    ///
+    /// The item will be uninstalled after the game quits.
     pub fn unsubscribe_item<F>(&self, published_file_id: PublishedFileId, cb: F)
     where
         F: FnOnce(Result<(), SteamError>) + 'static + Send,
@@ -480,17 +696,25 @@ impl<Manager> UGC<Manager> {
     }
 
     /// Gets the publisher file IDs of all currently subscribed items.
+    ///
+    /// Use this at startup to load all subscribed content. Items that were just subscribed
+    /// to may not be installed yet, check `item_state` before loading them.
     pub fn subscribed_items(&self) -> Vec<PublishedFileId> {
         unsafe {
             let count = sys::SteamAPI_ISteamUGC_GetNumSubscribedItems(self.ugc);
             let mut data: Vec<sys::PublishedFileId_t> = vec![0; count as usize];
             let gotten_count =
                 sys::SteamAPI_ISteamUGC_GetSubscribedItems(self.ugc, data.as_mut_ptr(), count);
-            debug_assert!(count == gotten_count);
-            data.into_iter().map(|v| PublishedFileId(v)).collect()
+            // The subscriptions can change in between both calls
+            data.truncate(gotten_count.min(count) as usize);
+            data.into_iter().map(PublishedFileId).collect()
         }
     }
 
+    /// Gets the current state of a workshop item on this client.
+    ///
+    /// An item can only be loaded once it is `INSTALLED`. If it `NEEDS_UPDATE`, use
+    /// `download_item` to fetch the latest version.
     pub fn item_state(&self, item: PublishedFileId) -> ItemState {
         unsafe {
             let state = sys::SteamAPI_ISteamUGC_GetItemState(self.ugc, item.0);
@@ -498,6 +722,9 @@ impl<Manager> UGC<Manager> {
         }
     }
 
+    /// Gets the download progress of a workshop item as bytes downloaded and total bytes.
+    ///
+    /// Returns `None` if the item isn't being downloaded. The total is 0 until the download has started.
     pub fn item_download_info(&self, item: PublishedFileId) -> Option<(u64, u64)> {
         unsafe {
             let mut current = 0u64;
@@ -515,6 +742,9 @@ impl<Manager> UGC<Manager> {
         }
     }
 
+    /// Gets information about the installed content of a workshop item.
+    ///
+    /// Returns `None` if the item isn't installed.
     pub fn item_install_info(&self, item: PublishedFileId) -> Option<InstallInfo> {
         unsafe {
             let mut size_on_disk = 0u64;
@@ -531,7 +761,8 @@ impl<Manager> UGC<Manager> {
                 Some(InstallInfo {
                     folder: CStr::from_ptr(folder.as_ptr() as *const _)
                         .to_string_lossy()
-                        .into_owned(),
+                        .into_owned()
+                        .into(),
                     size_on_disk,
                     timestamp,
                 })
@@ -541,11 +772,133 @@ impl<Manager> UGC<Manager> {
         }
     }
 
+    /// Downloads or updates a workshop item.
+    ///
+    /// If `high_priority` is true the item is downloaded immediately, pausing other downloads.
+    /// Otherwise it's queued behind the other downloads. The `DownloadItemResult` callback is
+    /// posted once the content is ready.
+    ///
+    /// Returns false if the item id is invalid or the user is not logged on.
     pub fn download_item(&self, item: PublishedFileId, high_priority: bool) -> bool {
         unsafe { sys::SteamAPI_ISteamUGC_DownloadItem(self.ugc, item.0, high_priority) }
     }
 
+    /// Votes an item up or down for the current user.
+    pub fn set_user_item_vote<F>(&self, published_file_id: PublishedFileId, vote_up: bool, cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call =
+                sys::SteamAPI_ISteamUGC_SetUserItemVote(self.ugc, published_file_id.0, vote_up);
+            register_call_result::<sys::SetUserItemVoteResult_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 8,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Gets how the current user voted on an item.
+    pub fn get_user_item_vote<F>(&self, published_file_id: PublishedFileId, cb: F)
+    where
+        F: FnOnce(Result<UserItemVote, SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamUGC_GetUserItemVote(self.ugc, published_file_id.0);
+            register_call_result::<sys::GetUserItemVoteResult_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 9,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else if v.m_bVotedUp {
+                        Ok(UserItemVote::Up)
+                    } else if v.m_bVotedDown {
+                        Ok(UserItemVote::Down)
+                    } else if v.m_bVoteSkipped {
+                        Ok(UserItemVote::Skipped)
+                    } else {
+                        Ok(UserItemVote::None)
+                    })
+                },
+            );
+        }
+    }
+
+    /// Adds a workshop item to the current user's favorites list
+    pub fn add_item_to_favorites<F>(&self, app_id: AppId, published_file_id: PublishedFileId, cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call =
+                sys::SteamAPI_ISteamUGC_AddItemToFavorites(self.ugc, app_id.0, published_file_id.0);
+            register_call_result::<sys::UserFavoriteItemsListChanged_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 7,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Removes a workshop item from the current user's favorites list
+    pub fn remove_item_from_favorites<F>(
+        &self,
+        app_id: AppId,
+        published_file_id: PublishedFileId,
+        cb: F,
+    ) where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamUGC_RemoveItemFromFavorites(
+                self.ugc,
+                app_id.0,
+                published_file_id.0,
+            );
+            register_call_result::<sys::UserFavoriteItemsListChanged_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 7,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
     /// Queries a list of workshop itmes, related to a user in some way (Ex. user's subscriptions, favorites, upvoted, ...)
+    ///
+    /// Results are paged, `page` starts at 1 and each page contains up to `RESULTS_PER_PAGE` items.
+    /// Some lists are only available for the current user, see `UserList`.
     pub fn query_user(
         &self,
         account: AccountId,
@@ -579,11 +932,49 @@ impl<Manager> UGC<Manager> {
         })
     }
 
+    /// Queries all workshop items matching the given type, sorted or filtered by `query_type`.
+    ///
+    /// Results are paged, `page` starts at 1 and each page contains up to `RESULTS_PER_PAGE` items.
+    /// Use `QueryResults::total_results` to find out how many pages there are.
+    pub fn query_all(
+        &self,
+        query_type: UGCQueryType,
+        item_type: UGCType,
+        appids: AppIDs,
+        page: u32,
+    ) -> Result<AllItemsQuery<Manager>, CreateQueryError> {
+        let res = unsafe {
+            sys::SteamAPI_ISteamUGC_CreateQueryAllUGCRequestPage(
+                self.ugc,
+                query_type.into(),
+                item_type.into(),
+                appids.creator_app_id().unwrap_or(AppId(0)).0,
+                appids.consumer_app_id().unwrap_or(AppId(0)).0,
+                page,
+            )
+        };
+
+        if res == UGCQueryHandleInvalid {
+            return Err(CreateQueryError);
+        }
+
+        Ok(AllItemsQuery {
+            ugc: self.ugc,
+            inner: Arc::clone(&self.inner),
+            handle: Some(res),
+        })
+    }
+
+    /// Queries the details of a known set of workshop items, e.g. the items of a saved mod preset.
+    ///
+    /// Returns `Err(CreateQueryError)` if `items` is empty.
     pub fn query_items(
         &self,
         mut items: Vec<PublishedFileId>,
     ) -> Result<ItemListDetailsQuery<Manager>, CreateQueryError> {
-        debug_assert!(items.len() > 0);
+        if items.is_empty() {
+            return Err(CreateQueryError);
+        }
 
         let res = unsafe {
             sys::SteamAPI_ISteamUGC_CreateQueryUGCDetailsRequest(
@@ -604,6 +995,7 @@ impl<Manager> UGC<Manager> {
         })
     }
 
+    /// Queries the details of a single workshop item
     pub fn query_item(
         &self,
         item: PublishedFileId,
@@ -629,23 +1021,45 @@ impl<Manager> UGC<Manager> {
         })
     }
 
+    /// Starts keeping the items the user is subscribed to installed and up to date.
+    ///
+    /// See `WorkshopSync` for details.
+    pub fn sync_subscribed_items(&self) -> WorkshopSync<Manager> {
+        self.sync_items(self.subscribed_items())
+    }
+
+    /// Starts keeping the given items installed and up to date.
+    ///
+    /// See `WorkshopSync` for details.
+    pub fn sync_items(&self, items: Vec<PublishedFileId>) -> WorkshopSync<Manager> {
+        WorkshopSync {
+            ugc: UGC {
+                ugc: self.ugc,
+                inner: self.inner.clone(),
+            },
+            items,
+            requested: HashSet::new(),
+            failed: HashSet::new(),
+        }
+    }
+
     /// **DELETES** the item from the Steam Workshop.
+    ///
+    /// Only the creator of the item can delete it.
     pub fn delete_item<F>(&self, published_file_id: PublishedFileId, cb: F)
     where
         F: FnOnce(Result<(), SteamError>) + 'static + Send,
     {
         unsafe {
             let api_call = sys::SteamAPI_ISteamUGC_DeleteItem(self.ugc, published_file_id.0);
-            register_call_result::<sys::DownloadItemResult_t, _, _>(
+            register_call_result::<sys::DeleteItemResult_t, _, _>(
                 &self.inner,
                 api_call,
-                CALLBACK_REMOTE_STORAGE_BASE_ID + 17,
+                CALLBACK_BASE_ID + 17,
                 move |v, io_error| {
                     cb(if io_error {
                         Err(SteamError::IOFailure)
-                    } else if v.m_eResult != sys::EResult::k_EResultNone
-                        && v.m_eResult != sys::EResult::k_EResultOK
-                    {
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
                         Err(v.m_eResult.into())
                     } else {
                         Ok(())
@@ -676,6 +1090,129 @@ impl UGC<ServerManager> {
     }
 }
 
+/// Downloads a set of workshop items and tracks them until all of them are installed.
+///
+/// Created by `UGC::sync_items` or `UGC::sync_subscribed_items`. `poll` should be called
+/// regularly, e.g. once per frame after running the callbacks. The sync doesn't register
+/// any callbacks itself, forward the `DownloadItemResult`s from your own callback to
+/// `download_result` so failed downloads are noticed.
+pub struct WorkshopSync<Manager> {
+    ugc: UGC<Manager>,
+    items: Vec<PublishedFileId>,
+    /// Items with a `download_item` call that didn't post a `DownloadItemResult` yet
+    requested: HashSet<PublishedFileId>,
+    failed: HashSet<PublishedFileId>,
+}
+
+/// The state of a `WorkshopSync`
+#[derive(Clone, Debug)]
+pub enum WorkshopSyncStatus {
+    /// Some items are still being downloaded
+    Syncing {
+        /// The number of items that aren't installed and up to date yet
+        pending: usize,
+        /// The number of bytes downloaded so far for the pending items
+        bytes_downloaded: u64,
+        /// The total number of bytes to download for the pending items, if known
+        bytes_total: u64,
+    },
+    /// Every item that didn't fail is installed and up to date.
+    ///
+    /// Maps the items to the folders they are installed in.
+    Ready(HashMap<PublishedFileId, PathBuf>),
+}
+
+impl<Manager> WorkshopSync<Manager> {
+    /// The items being synced
+    pub fn items(&self) -> &[PublishedFileId] {
+        &self.items
+    }
+
+    /// The items that couldn't be downloaded
+    ///
+    /// These are skipped by `poll` and are never part of the `Ready` result until
+    /// `retry_failed` is called.
+    pub fn failed(&self) -> impl Iterator<Item = PublishedFileId> + '_ {
+        self.failed.iter().copied()
+    }
+
+    /// Makes the next `poll` start downloading the failed items again
+    pub fn retry_failed(&mut self) {
+        self.failed.clear();
+    }
+
+    /// Records the result of a download started by this sync
+    ///
+    /// Results for items that aren't part of the sync are ignored.
+    pub fn download_result(&mut self, result: &DownloadItemResult) {
+        if !self.requested.remove(&result.published_file_id) {
+            return;
+        }
+        if result.error.is_some() {
+            self.failed.insert(result.published_file_id);
+        }
+    }
+
+    /// Checks the state of every item, starting downloads for anything missing or
+    /// out of date.
+    pub fn poll(&mut self) -> WorkshopSyncStatus {
+        let mut pending = 0;
+        let mut bytes_downloaded = 0;
+        let mut bytes_total = 0;
+
+        for &item in &self.items {
+            if self.failed.contains(&item) {
+                continue;
+            }
+            let state = self.ugc.item_state(item);
+            if state.contains(ItemState::INSTALLED) && !state.contains(ItemState::NEEDS_UPDATE) {
+                self.requested.remove(&item);
+                continue;
+            }
+            // Requested items stay pending until their `DownloadItemResult` arrives, even
+            // if steam hasn't marked them as downloading yet
+            if !state.intersects(ItemState::DOWNLOADING | ItemState::DOWNLOAD_PENDING)
+                && !self.requested.contains(&item)
+            {
+                if !self.ugc.download_item(item, false) {
+                    self.failed.insert(item);
+                    continue;
+                }
+                self.requested.insert(item);
+            }
+            pending += 1;
+            if let Some((downloaded, total)) = self.ugc.item_download_info(item) {
+                bytes_downloaded += downloaded;
+                bytes_total += total;
+            }
+        }
+
+        if pending > 0 {
+            return WorkshopSyncStatus::Syncing {
+                pending,
+                bytes_downloaded,
+                bytes_total,
+            };
+        }
+
+        let mut folders = HashMap::new();
+        for &item in &self.items {
+            if self.failed.contains(&item) {
+                continue;
+            }
+            match self.ugc.item_install_info(item) {
+                Some(info) => {
+                    folders.insert(item, info.folder);
+                }
+                None => {
+                    self.failed.insert(item);
+                }
+            }
+        }
+        WorkshopSyncStatus::Ready(folders)
+    }
+}
+
 /// A handle to update a published item
 pub struct UpdateHandle<Manager> {
     ugc: *mut sys::ISteamUGC,
@@ -685,6 +1222,7 @@ pub struct UpdateHandle<Manager> {
 }
 
 impl<Manager> UpdateHandle<Manager> {
+    /// Sets the title of the item, at most 128 bytes
     #[must_use]
     pub fn title(self, title: &str) -> Self {
         unsafe {
@@ -698,6 +1236,7 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    /// Sets the description of the item, at most 8000 bytes
     #[must_use]
     pub fn description(self, description: &str) -> Self {
         unsafe {
@@ -711,6 +1250,23 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    /// Sets the language of the title and description set by this update.
+    ///
+    /// Defaults to "english"
+    #[must_use]
+    pub fn language(self, language: &str) -> Self {
+        unsafe {
+            let language = CString::new(language).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_SetItemUpdateLanguage(
+                self.ugc,
+                self.handle,
+                language.as_ptr()
+            ));
+        }
+        self
+    }
+
+    /// Sets the primary preview image of the item, an image file that will be uploaded
     #[must_use]
     pub fn preview_path(self, path: &Path) -> Self {
         unsafe {
@@ -725,44 +1281,130 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    /// Adds an additional preview file of the given type to the item
+    ///
+    /// Videos must be added with `add_preview_video` instead.
     #[must_use]
-    pub fn content_path(self, path: &Path) -> Self {
+    pub fn add_preview_file(self, path: &Path, preview_type: PreviewFileType) -> Self {
         unsafe {
             let path = path.canonicalize().unwrap();
-            let content_path = CString::new(&*path.to_string_lossy()).unwrap();
-            assert!(sys::SteamAPI_ISteamUGC_SetItemContent(
+            let preview_path = CString::new(&*path.to_string_lossy()).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_AddItemPreviewFile(
                 self.ugc,
                 self.handle,
-                content_path.as_ptr()
+                preview_path.as_ptr(),
+                preview_type.into()
             ));
         }
         self
     }
 
+    /// Adds a YouTube video, given by its id, as an additional preview of the item
     #[must_use]
-    pub fn metadata(self, metadata: &str) -> Self {
+    pub fn add_preview_video(self, video_id: &str) -> Self {
         unsafe {
-            let metadata = CString::new(metadata).unwrap();
-            assert!(sys::SteamAPI_ISteamUGC_SetItemMetadata(
+            let video_id = CString::new(video_id).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_AddItemPreviewVideo(
                 self.ugc,
                 self.handle,
-                metadata.as_ptr()
+                video_id.as_ptr()
             ));
         }
         self
     }
 
-    pub fn visibility(self, visibility: remote_storage::PublishedFileVisibility) -> Self {
+    /// Replaces the additional preview file at the given index
+    ///
+    /// The index is the same as the one used by `QueryResults::additional_preview`.
+    #[must_use]
+    pub fn update_preview_file(self, index: u32, path: &Path) -> Self {
         unsafe {
-            assert!(sys::SteamAPI_ISteamUGC_SetItemVisibility(
+            let path = path.canonicalize().unwrap();
+            let preview_path = CString::new(&*path.to_string_lossy()).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_UpdateItemPreviewFile(
                 self.ugc,
                 self.handle,
-                visibility.into()
+                index,
+                preview_path.as_ptr()
             ));
         }
         self
     }
 
+    /// Replaces the additional preview video at the given index
+    #[must_use]
+    pub fn update_preview_video(self, index: u32, video_id: &str) -> Self {
+        unsafe {
+            let video_id = CString::new(video_id).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_UpdateItemPreviewVideo(
+                self.ugc,
+                self.handle,
+                index,
+                video_id.as_ptr()
+            ));
+        }
+        self
+    }
+
+    /// Removes the additional preview at the given index
+    #[must_use]
+    pub fn remove_preview(self, index: u32) -> Self {
+        unsafe {
+            assert!(sys::SteamAPI_ISteamUGC_RemoveItemPreview(
+                self.ugc,
+                self.handle,
+                index
+            ));
+        }
+        self
+    }
+
+    /// Sets the folder whose contents will be uploaded as the content of the item
+    #[must_use]
+    pub fn content_path(self, path: &Path) -> Self {
+        unsafe {
+            let path = path.canonicalize().unwrap();
+            let content_path = CString::new(&*path.to_string_lossy()).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_SetItemContent(
+                self.ugc,
+                self.handle,
+                content_path.as_ptr()
+            ));
+        }
+        self
+    }
+
+    /// Sets arbitrary developer metadata of the item, at most 5000 bytes.
+    ///
+    /// This can be read back with `include_metadata` on queries without downloading the item.
+    #[must_use]
+    pub fn metadata(self, metadata: &str) -> Self {
+        unsafe {
+            let metadata = CString::new(metadata).unwrap();
+            assert!(sys::SteamAPI_ISteamUGC_SetItemMetadata(
+                self.ugc,
+                self.handle,
+                metadata.as_ptr()
+            ));
+        }
+        self
+    }
+
+    /// Sets who can see the item
+    #[must_use]
+    pub fn visibility(self, visibility: remote_storage::PublishedFileVisibility) -> Self {
+        unsafe {
+            assert!(sys::SteamAPI_ISteamUGC_SetItemVisibility(
+                self.ugc,
+                self.handle,
+                visibility.into()
+            ));
+        }
+        self
+    }
+
+    /// Replaces the tags of the item
+    #[must_use]
     pub fn tags<S: AsRef<str>>(self, tags: Vec<S>) -> Self {
         unsafe {
             let mut tags = SteamParamStringArray::new(&tags);
@@ -775,6 +1417,7 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    #[must_use]
     pub fn add_key_value_tag(self, key: &str, value: &str) -> Self {
         unsafe {
             let key = CString::new(key).unwrap();
@@ -789,6 +1432,7 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    #[must_use]
     pub fn remove_key_value_tag(self, key: &str) -> Self {
         unsafe {
             let key = CString::new(key).unwrap();
@@ -801,6 +1445,7 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    #[must_use]
     pub fn remove_all_key_value_tags(self) -> Self {
         unsafe {
             assert!(sys::SteamAPI_ISteamUGC_RemoveAllItemKeyValueTags(
@@ -811,6 +1456,10 @@ impl<Manager> UpdateHandle<Manager> {
         self
     }
 
+    /// Uploads the changes made to the item.
+    ///
+    /// The callback receives the id of the item and whether the user still needs to accept the
+    /// workshop legal agreement. Use the returned handle to track the progress of the upload.
     pub fn submit<F>(self, change_note: Option<&str>, cb: F) -> UpdateWatchHandle<Manager>
     where
         F: FnOnce(Result<(PublishedFileId, bool), SteamError>) + 'static + Send,
@@ -858,6 +1507,12 @@ unsafe impl<Manager> Send for UpdateWatchHandle<Manager> {}
 unsafe impl<Manager> Sync for UpdateWatchHandle<Manager> {}
 
 impl<Manager> UpdateWatchHandle<Manager> {
+    /// Returns the current status of the update, the number of bytes processed and the
+    /// total number of bytes of the current step.
+    ///
+    /// Poll this regularly to display the progress of an upload. The byte counts are only
+    /// meaningful while preparing or uploading content. Once the update has finished, the
+    /// status is `UpdateStatus::Invalid`.
     pub fn progress(&self) -> (UpdateStatus, u64, u64) {
         unsafe {
             let mut progress = 0;
@@ -868,252 +1523,326 @@ impl<Manager> UpdateWatchHandle<Manager> {
                 &mut progress,
                 &mut total,
             );
-            let status = match status {
-                sys::EItemUpdateStatus::k_EItemUpdateStatusInvalid => UpdateStatus::Invalid,
-                sys::EItemUpdateStatus::k_EItemUpdateStatusPreparingConfig => {
-                    UpdateStatus::PreparingConfig
-                }
-                sys::EItemUpdateStatus::k_EItemUpdateStatusPreparingContent => {
-                    UpdateStatus::PreparingContent
-                }
-                sys::EItemUpdateStatus::k_EItemUpdateStatusUploadingContent => {
-                    UpdateStatus::UploadingContent
-                }
-                sys::EItemUpdateStatus::k_EItemUpdateStatusUploadingPreviewFile => {
-                    UpdateStatus::UploadingPreviewFile
-                }
-                sys::EItemUpdateStatus::k_EItemUpdateStatusCommittingChanges => {
-                    UpdateStatus::CommittingChanges
-                }
-                _ => unreachable!(),
-            };
-            (status, progress, total)
+            (status.into(), progress, total)
         }
     }
 }
 
+/// The step an item update is currently at
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UpdateStatus {
+    /// The update handle is invalid or the update has finished
     Invalid,
+    /// Processing the configuration data
     PreparingConfig,
+    /// Reading and processing the content files
     PreparingContent,
+    /// Uploading the content changes to Steam
     UploadingContent,
+    /// Uploading the new preview file image
     UploadingPreviewFile,
+    /// Committing all changes
     CommittingChanges,
 }
 
-/// Query object from `query_user`, to allow for more filtering.
-pub struct UserListQuery<Manager> {
-    ugc: *mut sys::ISteamUGC,
-    inner: Arc<Inner<Manager>>,
-
-    // Note: this is always filled except in `fetch`, where it must be taken
-    // to prevent the handle from being dropped when this query is dropped.
-    handle: Option<sys::UGCQueryHandle_t>,
-}
-impl<Manager> Drop for UserListQuery<Manager> {
-    fn drop(&mut self) {
-        if let Some(handle) = self.handle.as_mut() {
-            unsafe {
-                sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(self.ugc, *handle);
+impl From<sys::EItemUpdateStatus> for UpdateStatus {
+    fn from(status: sys::EItemUpdateStatus) -> Self {
+        match status {
+            sys::EItemUpdateStatus::k_EItemUpdateStatusPreparingConfig => {
+                UpdateStatus::PreparingConfig
+            }
+            sys::EItemUpdateStatus::k_EItemUpdateStatusPreparingContent => {
+                UpdateStatus::PreparingContent
             }
+            sys::EItemUpdateStatus::k_EItemUpdateStatusUploadingContent => {
+                UpdateStatus::UploadingContent
+            }
+            sys::EItemUpdateStatus::k_EItemUpdateStatusUploadingPreviewFile => {
+                UpdateStatus::UploadingPreviewFile
+            }
+            sys::EItemUpdateStatus::k_EItemUpdateStatusCommittingChanges => {
+                UpdateStatus::CommittingChanges
+            }
+            _ => UpdateStatus::Invalid,
         }
     }
 }
-impl<Manager> UserListQuery<Manager> {
-    /// Excludes items with a specific tag.
-    ///
-    /// Panics if `tag` could not be converted to a `CString`.
-    pub fn exclude_tag(self, tag: &str) -> Self {
-        let cstr = CString::new(tag)
-            .expect("String passed to exclude_tag could not be converted to a c string");
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_AddExcludedTag(self.ugc, self.handle.unwrap(), cstr.as_ptr())
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Only include items with a specific tag.
-    ///
-    /// Panics if `tag` could not be converted to a `CString`.
-    pub fn require_tag(self, tag: &str) -> Self {
-        let cstr = CString::new(tag)
-            .expect("String passed to require_tag could not be converted to a c string");
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_AddRequiredTag(self.ugc, self.handle.unwrap(), cstr.as_ptr())
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Sets how to match tags added by `require_tag`. If `true`, then any tag may match. If `false`, all required tags must match.
-    pub fn any_required(self, any: bool) -> Self {
-        let ok =
-            unsafe { sys::SteamAPI_ISteamUGC_SetMatchAnyTag(self.ugc, self.handle.unwrap(), any) };
-        debug_assert!(ok);
-        self
-    }
 
-    /// Sets the language to return the title and description in for the items on a pending UGC Query.
-    ///
-    /// Defaults to "english"
-    pub fn language(self, language: &str) -> Self {
-        let cstr = CString::new(language)
-            .expect("String passed to language could not be converted to a c string");
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetLanguage(self.ugc, self.handle.unwrap(), cstr.as_ptr())
-        };
-        debug_assert!(ok);
-        self
-    }
+/// Implements `Drop`, the setters and the `fetch` family shared by the UGC query types,
+/// which all have `ugc`, `inner` and `handle` fields.
+///
+/// `ItemListDetailsQuery` and `ItemDetailsQuery` look up items by id, so they only get
+/// the parts that apply to them.
+macro_rules! ugc_query_common {
+    // `Drop`, the result options and `fetch`, shared by every query
+    (@base $query:ident) => {
+        impl<Manager> Drop for $query<Manager> {
+            fn drop(&mut self) {
+                if let Some(handle) = self.handle.as_mut() {
+                    unsafe {
+                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(self.ugc, *handle);
+                    }
+                }
+            }
+        }
+        impl<Manager> $query<Manager> {
+            /// Sets the language to return the title and description in for the items on a pending UGC Query.
+            ///
+            /// Defaults to "english"
+            pub fn language(self, language: &str) -> Self {
+                let cstr = CString::new(language)
+                    .expect("String passed to language could not be converted to a c string");
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetLanguage(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        cstr.as_ptr(),
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Sets whether results will be returned from the cache for the specific period of time on a pending UGC Query.
-    ///
-    /// Age is in seconds.
-    pub fn allow_cached_response(self, max_age_s: u32) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetAllowCachedResponse(
-                self.ugc,
-                self.handle.unwrap(),
-                max_age_s,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
+            /// Sets whether results will be returned from the cache for the specific period of time on a pending UGC Query.
+            ///
+            /// Age is in seconds.
+            pub fn allow_cached_response(self, max_age_s: u32) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetAllowCachedResponse(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        max_age_s,
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Include the full description in results
-    pub fn include_long_desc(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnLongDescription(
-                self.ugc,
-                self.handle.unwrap(),
-                include,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
+            /// Include the full description in results
+            pub fn include_long_desc(self, include: bool) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetReturnLongDescription(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        include,
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Include children in results
-    pub fn include_children(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnChildren(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
+            /// Include children in results
+            pub fn include_children(self, include: bool) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetReturnChildren(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        include,
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Include metadata in results
-    pub fn include_metadata(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnMetadata(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
+            /// Include metadata in results
+            pub fn include_metadata(self, include: bool) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetReturnMetadata(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        include,
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Include additional previews in results
-    pub fn include_additional_previews(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnAdditionalPreviews(
-                self.ugc,
-                self.handle.unwrap(),
-                include,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
+            /// Include additional previews in results
+            pub fn include_additional_previews(self, include: bool) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetReturnAdditionalPreviews(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        include,
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Include key value tags in results
-    pub fn include_key_value_tags(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnKeyValueTags(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
+            /// Runs the query
+            pub fn fetch<F>(mut self, cb: F)
+            where
+                F: for<'a> FnOnce(Result<QueryResults<'a>, SteamError>) + 'static + Send,
+            {
+                let ugc = self.ugc;
+                let inner = Arc::clone(&self.inner);
+                let handle = self.handle.take().unwrap();
+                mem::drop(self);
+
+                unsafe {
+                    let api_call = sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(ugc, handle);
+                    register_call_result::<sys::SteamUGCQueryCompleted_t, _, _>(
+                        &inner,
+                        api_call,
+                        CALLBACK_BASE_ID + 1,
+                        move |v, io_error| {
+                            let ugc = sys::SteamAPI_SteamUGC_v016();
+                            if io_error {
+                                sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
+                                cb(Err(SteamError::IOFailure));
+                                return;
+                            } else if v.m_eResult != sys::EResult::k_EResultOK {
+                                sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
+                                cb(Err(v.m_eResult.into()));
+                                return;
+                            }
+
+                            let result = QueryResults {
+                                ugc,
+                                handle,
+                                num_results_returned: v.m_unNumResultsReturned,
+                                num_results_total: v.m_unTotalMatchingResults,
+                                was_cached: v.m_bCachedData,
+                                _phantom: Default::default(),
+                            };
+                            cb(Ok(result));
+                        },
+                    );
+                }
+            }
+        }
+    };
+    // Options for queries that can return several items
+    (@list $query:ident) => {
+        impl<Manager> $query<Manager> {
+            /// Sets how to match tags added by `require_tag`. If `true`, then any tag may match. If `false`, all required tags must match.
+            pub fn any_required(self, any: bool) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetMatchAnyTag(self.ugc, self.handle.unwrap(), any)
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Runs the query
-    pub fn fetch<F>(mut self, cb: F)
-    where
-        F: for<'a> FnOnce(Result<QueryResults<'a>, SteamError>) + 'static + Send,
-    {
-        let ugc = self.ugc;
-        let inner = Arc::clone(&self.inner);
-        let handle = self.handle.take().unwrap();
-        mem::drop(self);
+            /// Include key value tags in results
+            pub fn include_key_value_tags(self, include: bool) -> Self {
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_SetReturnKeyValueTags(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        include,
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-        unsafe {
-            let api_call = sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(ugc, handle);
-            register_call_result::<sys::SteamUGCQueryCompleted_t, _, _>(
-                &inner,
-                api_call,
-                CALLBACK_BASE_ID + 1,
-                move |v, io_error| {
-                    let ugc = sys::SteamAPI_SteamUGC_v016();
-                    if io_error {
-                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
-                        cb(Err(SteamError::IOFailure));
-                        return;
-                    } else if v.m_eResult != sys::EResult::k_EResultOK {
-                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
-                        cb(Err(v.m_eResult.into()));
-                        return;
-                    }
+            /// Runs the query, only fetching the total number of results.
+            pub fn fetch_total<F>(self, cb: F)
+            where
+                F: Fn(Result<u32, SteamError>) + 'static + Send,
+            {
+                unsafe {
+                    let ok = sys::SteamAPI_ISteamUGC_SetReturnTotalOnly(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        true,
+                    );
+                    debug_assert!(ok);
+                }
 
-                    let result = QueryResults {
-                        ugc,
-                        handle,
-                        num_results_returned: v.m_unNumResultsReturned,
-                        num_results_total: v.m_unTotalMatchingResults,
-                        was_cached: v.m_bCachedData,
-                        _phantom: Default::default(),
-                    };
-                    cb(Ok(result));
-                },
-            );
+                self.fetch(move |res| cb(res.map(|qr| qr.total_results())))
+            }
         }
-    }
+    };
+    // Tag filters, which steam only supports for user and all items queries
+    (@filters $query:ident) => {
+        impl<Manager> $query<Manager> {
+            /// Excludes items with a specific tag.
+            ///
+            /// Panics if `tag` could not be converted to a `CString`.
+            pub fn exclude_tag(self, tag: &str) -> Self {
+                let cstr = CString::new(tag)
+                    .expect("String passed to exclude_tag could not be converted to a c string");
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_AddExcludedTag(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        cstr.as_ptr(),
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Runs the query, only fetching the total number of results.
-    pub fn fetch_total<F>(self, cb: F)
-    where
-        F: Fn(Result<u32, SteamError>) + 'static + Send,
-    {
-        unsafe {
-            let ok =
-                sys::SteamAPI_ISteamUGC_SetReturnTotalOnly(self.ugc, self.handle.unwrap(), true);
-            debug_assert!(ok);
-        }
+            /// Only include items with a specific tag.
+            ///
+            /// Panics if `tag` could not be converted to a `CString`.
+            pub fn require_tag(self, tag: &str) -> Self {
+                let cstr = CString::new(tag)
+                    .expect("String passed to require_tag could not be converted to a c string");
+                let ok = unsafe {
+                    sys::SteamAPI_ISteamUGC_AddRequiredTag(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        cstr.as_ptr(),
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-        self.fetch(move |res| cb(res.map(|qr| qr.total_results())))
-    }
+            /// Only include items that have at least one of the tags in `tags`.
+            ///
+            /// Every call adds another group, all groups must match.
+            pub fn require_tag_group<S: AsRef<str>>(self, tags: &[S]) -> Self {
+                let ok = unsafe {
+                    let mut tags = SteamParamStringArray::new(tags);
+                    sys::SteamAPI_ISteamUGC_AddRequiredTagGroup(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        &tags.as_raw(),
+                    )
+                };
+                debug_assert!(ok);
+                self
+            }
 
-    /// Runs the query, only fetching the IDs.
-    pub fn fetch_ids<F>(self, cb: F)
-    where
-        F: Fn(Result<Vec<PublishedFileId>, SteamError>) + 'static + Send,
-    {
-        unsafe {
-            let ok = sys::SteamAPI_ISteamUGC_SetReturnOnlyIDs(self.ugc, self.handle.unwrap(), true);
-            debug_assert!(ok);
-        }
+            /// Runs the query, only fetching the IDs.
+            pub fn fetch_ids<F>(self, cb: F)
+            where
+                F: Fn(Result<Vec<PublishedFileId>, SteamError>) + 'static + Send,
+            {
+                unsafe {
+                    let ok = sys::SteamAPI_ISteamUGC_SetReturnOnlyIDs(
+                        self.ugc,
+                        self.handle.unwrap(),
+                        true,
+                    );
+                    debug_assert!(ok);
+                }
 
-        self.fetch(move |res| {
-            cb(res.map(|qr| {
-                qr.iter()
-                    .filter_map(|v| v.map(|v| PublishedFileId(v.published_file_id.0)))
-                    .collect::<Vec<_>>()
-            }))
-        })
-    }
+                self.fetch(move |res| {
+                    cb(res.map(|qr| {
+                        qr.iter()
+                            .filter_map(|v| v.map(|v| PublishedFileId(v.published_file_id.0)))
+                            .collect::<Vec<_>>()
+                    }))
+                })
+            }
+        }
+    };
+    ($query:ident) => {
+        ugc_query_common!(@base $query);
+        ugc_query_common!(@list $query);
+        ugc_query_common!(@filters $query);
+    };
 }
 
-/// Query object from `query_items`, to allow for more filtering.
-pub struct ItemListDetailsQuery<Manager> {
+/// Query object from `query_user`, to allow for more filtering.
+pub struct UserListQuery<Manager> {
     ugc: *mut sys::ISteamUGC,
     inner: Arc<Inner<Manager>>,
 
@@ -1121,161 +1850,56 @@ pub struct ItemListDetailsQuery<Manager> {
     // to prevent the handle from being dropped when this query is dropped.
     handle: Option<sys::UGCQueryHandle_t>,
 }
-impl<Manager> Drop for ItemListDetailsQuery<Manager> {
-    fn drop(&mut self) {
-        if let Some(handle) = self.handle.as_mut() {
-            unsafe {
-                sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(self.ugc, *handle);
-            }
-        }
-    }
+ugc_query_common!(UserListQuery);
+
+/// Query object from `query_all`, to allow for more filtering.
+pub struct AllItemsQuery<Manager> {
+    ugc: *mut sys::ISteamUGC,
+    inner: Arc<Inner<Manager>>,
+
+    // Note: this is always filled except in `fetch`, where it must be taken
+    // to prevent the handle from being dropped when this query is dropped.
+    handle: Option<sys::UGCQueryHandle_t>,
 }
-impl<Manager> ItemListDetailsQuery<Manager> {
-    /// Sets how to match tags added by `require_tag`. If `true`, then any tag may match. If `false`, all required tags must match.
-    pub fn any_required(self, any: bool) -> Self {
-        let ok =
-            unsafe { sys::SteamAPI_ISteamUGC_SetMatchAnyTag(self.ugc, self.handle.unwrap(), any) };
-        debug_assert!(ok);
-        self
-    }
+ugc_query_common!(AllItemsQuery);
 
-    /// Sets the language to return the title and description in for the items on a pending UGC Query.
+impl<Manager> AllItemsQuery<Manager> {
+    /// Only include items that match the search text.
     ///
-    /// Defaults to "english"
-    pub fn language(self, language: &str) -> Self {
-        let cstr = CString::new(language)
-            .expect("String passed to language could not be converted to a c string");
+    /// Panics if `text` could not be converted to a `CString`.
+    pub fn search_text(self, text: &str) -> Self {
+        let cstr = CString::new(text)
+            .expect("String passed to search_text could not be converted to a c string");
         let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetLanguage(self.ugc, self.handle.unwrap(), cstr.as_ptr())
+            sys::SteamAPI_ISteamUGC_SetSearchText(self.ugc, self.handle.unwrap(), cstr.as_ptr())
         };
         debug_assert!(ok);
         self
     }
 
-    /// Sets whether results will be returned from the cache for the specific period of time on a pending UGC Query.
+    /// Sets the number of days to consider when ranking items by trend.
     ///
-    /// Age is in seconds.
-    pub fn allow_cached_response(self, max_age_s: u32) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetAllowCachedResponse(
-                self.ugc,
-                self.handle.unwrap(),
-                max_age_s,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include the full description in results
-    pub fn include_long_desc(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnLongDescription(
-                self.ugc,
-                self.handle.unwrap(),
-                include,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include children in results
-    pub fn include_children(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnChildren(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include metadata in results
-    pub fn include_metadata(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnMetadata(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include additional previews in results
-    pub fn include_additional_previews(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnAdditionalPreviews(
-                self.ugc,
-                self.handle.unwrap(),
-                include,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include key value tags in results
-    pub fn include_key_value_tags(self, include: bool) -> Self {
+    /// Only used with `UGCQueryType::RankedByTrend`.
+    pub fn ranked_by_trend_days(self, days: u32) -> Self {
         let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnKeyValueTags(self.ugc, self.handle.unwrap(), include)
+            sys::SteamAPI_ISteamUGC_SetRankedByTrendDays(self.ugc, self.handle.unwrap(), days)
         };
         debug_assert!(ok);
         self
     }
+}
 
-    /// Runs the query
-    pub fn fetch<F>(mut self, cb: F)
-    where
-        F: for<'a> FnOnce(Result<QueryResults<'a>, SteamError>) + 'static + Send,
-    {
-        let ugc = self.ugc;
-        let inner = Arc::clone(&self.inner);
-        let handle = self.handle.take().unwrap();
-        mem::drop(self);
-
-        unsafe {
-            let api_call = sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(ugc, handle);
-            register_call_result::<sys::SteamUGCQueryCompleted_t, _, _>(
-                &inner,
-                api_call,
-                CALLBACK_BASE_ID + 1,
-                move |v, io_error| {
-                    let ugc = sys::SteamAPI_SteamUGC_v016();
-                    if io_error {
-                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
-                        cb(Err(SteamError::IOFailure));
-                        return;
-                    } else if v.m_eResult != sys::EResult::k_EResultOK {
-                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
-                        cb(Err(v.m_eResult.into()));
-                        return;
-                    }
-
-                    let result = QueryResults {
-                        ugc,
-                        handle,
-                        num_results_returned: v.m_unNumResultsReturned,
-                        num_results_total: v.m_unTotalMatchingResults,
-                        was_cached: v.m_bCachedData,
-                        _phantom: Default::default(),
-                    };
-                    cb(Ok(result));
-                },
-            );
-        }
-    }
-
-    /// Runs the query, only fetching the total number of results.
-    pub fn fetch_total<F>(self, cb: F)
-    where
-        F: Fn(Result<u32, SteamError>) + 'static + Send,
-    {
-        unsafe {
-            let ok =
-                sys::SteamAPI_ISteamUGC_SetReturnTotalOnly(self.ugc, self.handle.unwrap(), true);
-            debug_assert!(ok);
-        }
+/// Query object from `query_items`, to allow for more filtering.
+pub struct ItemListDetailsQuery<Manager> {
+    ugc: *mut sys::ISteamUGC,
+    inner: Arc<Inner<Manager>>,
 
-        self.fetch(move |res| cb(res.map(|qr| qr.total_results())))
-    }
+    // Note: this is always filled except in `fetch`, where it must be taken
+    // to prevent the handle from being dropped when this query is dropped.
+    handle: Option<sys::UGCQueryHandle_t>,
 }
+ugc_query_common!(@base ItemListDetailsQuery);
+ugc_query_common!(@list ItemListDetailsQuery);
 
 /// Query object from `query_item`, to allow for more filtering.
 pub struct ItemDetailsQuery<Manager> {
@@ -1286,130 +1910,7 @@ pub struct ItemDetailsQuery<Manager> {
     // to prevent the handle from being dropped when this query is dropped.
     handle: Option<sys::UGCQueryHandle_t>,
 }
-impl<Manager> Drop for ItemDetailsQuery<Manager> {
-    fn drop(&mut self) {
-        if let Some(handle) = self.handle.as_mut() {
-            unsafe {
-                sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(self.ugc, *handle);
-            }
-        }
-    }
-}
-impl<Manager> ItemDetailsQuery<Manager> {
-    /// Sets the language to return the title and description in for the items on a pending UGC Query.
-    ///
-    /// Defaults to "english"
-    pub fn language(self, language: &str) -> Self {
-        let cstr = CString::new(language)
-            .expect("String passed to language could not be converted to a c string");
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetLanguage(self.ugc, self.handle.unwrap(), cstr.as_ptr())
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Sets whether results will be returned from the cache for the specific period of time on a pending UGC Query.
-    ///
-    /// Age is in seconds.
-    pub fn allow_cached_response(self, max_age_s: u32) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetAllowCachedResponse(
-                self.ugc,
-                self.handle.unwrap(),
-                max_age_s,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include the full description in results
-    pub fn include_long_desc(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnLongDescription(
-                self.ugc,
-                self.handle.unwrap(),
-                include,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include children in results
-    pub fn include_children(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnChildren(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include metadata in results
-    pub fn include_metadata(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnMetadata(self.ugc, self.handle.unwrap(), include)
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Include additional previews in results
-    pub fn include_additional_previews(self, include: bool) -> Self {
-        let ok = unsafe {
-            sys::SteamAPI_ISteamUGC_SetReturnAdditionalPreviews(
-                self.ugc,
-                self.handle.unwrap(),
-                include,
-            )
-        };
-        debug_assert!(ok);
-        self
-    }
-
-    /// Runs the query
-    pub fn fetch<F>(mut self, cb: F)
-    where
-        F: for<'a> FnOnce(Result<QueryResults<'a>, SteamError>) + 'static + Send,
-    {
-        let ugc = self.ugc;
-        let inner = Arc::clone(&self.inner);
-        let handle = self.handle.take().unwrap();
-        mem::drop(self);
-
-        unsafe {
-            let api_call = sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(ugc, handle);
-            register_call_result::<sys::SteamUGCQueryCompleted_t, _, _>(
-                &inner,
-                api_call,
-                CALLBACK_BASE_ID + 1,
-                move |v, io_error| {
-                    let ugc = sys::SteamAPI_SteamUGC_v016();
-                    if io_error {
-                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
-                        cb(Err(SteamError::IOFailure));
-                        return;
-                    } else if v.m_eResult != sys::EResult::k_EResultOK {
-                        sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle);
-                        cb(Err(v.m_eResult.into()));
-                        return;
-                    }
-
-                    let result = QueryResults {
-                        ugc,
-                        handle,
-                        num_results_returned: v.m_unNumResultsReturned,
-                        num_results_total: v.m_unTotalMatchingResults,
-                        was_cached: v.m_bCachedData,
-                        _phantom: Default::default(),
-                    };
-                    cb(Ok(result));
-                },
-            );
-        }
-    }
-}
+ugc_query_common!(@base ItemDetailsQuery);
 
 /// Query results
 pub struct QueryResults<'a> {
@@ -1588,6 +2089,54 @@ impl<'a> QueryResults<'a> {
         }
     }
 
+    /// Returns the number of additional previews of the item at the specified index.
+    ///
+    /// You must call `include_additional_previews(true)` before fetching the query for this to work.
+    pub fn num_additional_previews(&self, index: u32) -> u32 {
+        unsafe {
+            sys::SteamAPI_ISteamUGC_GetQueryUGCNumAdditionalPreviews(self.ugc, self.handle, index)
+        }
+    }
+
+    /// Gets an additional preview of the item at the specified index.
+    ///
+    /// Returns None if either index was out of bounds.
+    pub fn additional_preview(&self, index: u32, preview_index: u32) -> Option<AdditionalPreview> {
+        let mut url = [0 as c_char; 4096];
+        let mut file_name = [0 as c_char; 260];
+        let mut preview_type = sys::EItemPreviewType::k_EItemPreviewType_Image;
+
+        let ok = unsafe {
+            sys::SteamAPI_ISteamUGC_GetQueryUGCAdditionalPreview(
+                self.ugc,
+                self.handle,
+                index,
+                preview_index,
+                url.as_mut_ptr(),
+                url.len() as _,
+                file_name.as_mut_ptr(),
+                file_name.len() as _,
+                &mut preview_type,
+            )
+        };
+
+        if ok {
+            Some(unsafe {
+                AdditionalPreview {
+                    url_or_video_id: CStr::from_ptr(url.as_ptr() as *const _)
+                        .to_string_lossy()
+                        .into_owned(),
+                    original_file_name: CStr::from_ptr(file_name.as_ptr() as *const _)
+                        .to_string_lossy()
+                        .into_owned(),
+                    preview_type: preview_type.into(),
+                }
+            })
+        } else {
+            None
+        }
+    }
+
     /// Returns the number of key value tags associated with the item at the specified index.
     pub fn key_value_tags(&self, index: u32) -> u32 {
         unsafe { sys::SteamAPI_ISteamUGC_GetQueryUGCNumKeyValueTags(self.ugc, self.handle, index) }
@@ -1629,7 +2178,8 @@ impl<'a> QueryResults<'a> {
 
     /// Gets the developer-set metadata associated with the item at the specified index.
     ///
-    /// This is returned as a vector of raw bytes.
+    /// This is returned as a vector of raw bytes. You must call `include_metadata(true)`
+    /// before fetching the query for this to work.
     pub fn get_metadata(&self, index: u32) -> Option<Vec<u8>> {
         let mut metadata = [0 as c_char; sys::k_cchDeveloperMetadataMax as usize];
 
@@ -1644,11 +2194,18 @@ impl<'a> QueryResults<'a> {
         };
 
         if ok {
-            let metadata = unsafe { CStr::from_ptr(metadata.as_ptr() as *const _).to_bytes() };
-            if metadata.is_empty() {
+            let metadata = unsafe {
+                std::slice::from_raw_parts(metadata.as_ptr() as *const u8, metadata.len())
+            };
+            // The metadata may fill the whole buffer without a nul terminator
+            let len = metadata
+                .iter()
+                .position(|&b| b == 0)
+                .unwrap_or(metadata.len());
+            if len == 0 {
                 None
             } else {
-                Some(metadata.to_vec())
+                Some(metadata[..len].to_vec())
             }
         } else {
             None
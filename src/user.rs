@@ -2,10 +2,12 @@ use super::*;
 #[cfg(test)]
 use serial_test_derive::serial;
 
+const CALLBACK_BASE_ID: i32 = 100;
+
 /// Access to the steam user interface
 pub struct User<Manager> {
     pub(crate) user: *mut sys::ISteamUser,
-    pub(crate) _inner: Arc<Inner<Manager>>,
+    pub(crate) inner: Arc<Inner<Manager>>,
 }
 
 impl<Manager> User<Manager> {
@@ -56,6 +58,59 @@ impl<Manager> User<Manager> {
         }
     }
 
+    /// Requests an encrypted app ticket that proves the user owns the game.
+    ///
+    /// `data` is included in the ticket, e.g. to bind it to a session. Once
+    /// the callback returns successfully the ticket can be retrieved with
+    /// `encrypted_app_ticket` and sent to a backend that decrypts it with
+    /// the app's secret key.
+    pub fn request_encrypted_app_ticket<F>(&self, data: &[u8], cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamUser_RequestEncryptedAppTicket(
+                self.user,
+                data.as_ptr() as *mut _,
+                data.len() as _,
+            );
+            register_call_result::<sys::EncryptedAppTicketResponse_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 54,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Returns the encrypted app ticket from the last successful call to
+    /// `request_encrypted_app_ticket`.
+    pub fn encrypted_app_ticket(&self) -> Option<Vec<u8>> {
+        unsafe {
+            let mut ticket = vec![0; 1024];
+            let mut ticket_len = 0;
+            if sys::SteamAPI_ISteamUser_GetEncryptedAppTicket(
+                self.user,
+                ticket.as_mut_ptr() as *mut _,
+                ticket.len() as _,
+                &mut ticket_len,
+            ) {
+                ticket.truncate(ticket_len as usize);
+                Some(ticket)
+            } else {
+                None
+            }
+        }
+    }
+
     /// Authenticate the ticket from the steam ID to make sure it is
     /// valid and not reused.
     ///
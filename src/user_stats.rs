@@ -1,6 +1,8 @@
+mod schema;
 mod stat_callback;
 pub mod stats;
 
+pub use self::schema::*;
 pub use self::stat_callback::*;
 use super::*;
 #[cfg(test)]
@@ -15,6 +17,9 @@ pub struct UserStats<Manager> {
 const CALLBACK_BASE_ID: i32 = 1100;
 
 impl<Manager> UserStats<Manager> {
+    /// Looks up a leaderboard by name.
+    ///
+    /// The callback receives `None` if no leaderboard with that name exists.
     pub fn find_leaderboard<F>(&self, name: &str, cb: F)
     where
         F: FnOnce(Result<Option<Leaderboard>, SteamError>) + 'static + Send,
@@ -44,6 +49,10 @@ impl<Manager> UserStats<Manager> {
         }
     }
 
+    /// Looks up a leaderboard by name, creating it with the given settings if it
+    /// doesn't exist yet.
+    ///
+    /// The settings of an existing leaderboard are left untouched.
     pub fn find_or_create_leaderboard<F>(
         &self,
         name: &str,
@@ -102,6 +111,11 @@ impl<Manager> UserStats<Manager> {
         }
     }
 
+    /// Uploads a score of the current user to the leaderboard.
+    ///
+    /// `details` can hold up to 64 extra values, e.g. to describe how the score was
+    /// achieved, more fail with `SteamError::InvalidParameter`. The callback receives
+    /// `None` if the upload failed.
     pub fn upload_leaderboard_score<F>(
         &self,
         leaderboard: &Leaderboard,
@@ -112,6 +126,10 @@ impl<Manager> UserStats<Manager> {
     ) where
         F: FnOnce(Result<Option<LeaderboardScoreUploaded>, SteamError>) + 'static + Send,
     {
+        if details.len() > sys::k_cLeaderboardDetailsMax as usize {
+            cb(Err(SteamError::InvalidParameter));
+            return;
+        }
         unsafe {
             let method = match method {
                 UploadScoreMethod::KeepBest => {
@@ -153,6 +171,10 @@ impl<Manager> UserStats<Manager> {
         }
     }
 
+    /// Downloads a range of entries from the leaderboard.
+    ///
+    /// See `LeaderboardDataRequest` for how `start` and `end` are interpreted, both are
+    /// inclusive. At most `max_details_len` details are returned per entry.
     pub fn download_leaderboard_entries<F>(
         &self,
         leaderboard: &Leaderboard,
@@ -192,30 +214,88 @@ impl<Manager> UserStats<Manager> {
                     cb(if io_error {
                         Err(SteamError::IOFailure)
                     } else {
-                        let len = v.m_cEntryCount;
-                        let mut entries = Vec::with_capacity(len as usize);
-                        for idx in 0..len {
-                            let mut entry: sys::LeaderboardEntry_t = std::mem::zeroed();
-                            let mut details = Vec::with_capacity(max_details_len);
-
-                            sys::SteamAPI_ISteamUserStats_GetDownloadedLeaderboardEntry(
-                                user_stats as *mut _,
-                                v.m_hSteamLeaderboardEntries,
-                                idx,
-                                &mut entry,
-                                details.as_mut_ptr(),
-                                max_details_len as _,
-                            );
-                            details.set_len(entry.m_cDetails as usize);
-
-                            entries.push(LeaderboardEntry {
-                                user: SteamId(entry.m_steamIDUser.m_steamid.m_unAll64Bits),
-                                global_rank: entry.m_nGlobalRank,
-                                score: entry.m_nScore,
-                                details,
-                            })
-                        }
-                        Ok(entries)
+                        Ok(downloaded_entries(user_stats as *mut _, v, max_details_len))
+                    })
+                },
+            );
+        }
+    }
+
+    /// Downloads the entries of specific users from the leaderboard, e.g. the players
+    /// of the current match.
+    ///
+    /// Users without an entry are left out. At most 100 users can be requested at once,
+    /// more fail with `SteamError::InvalidParameter`.
+    pub fn download_leaderboard_entries_for_users<F>(
+        &self,
+        leaderboard: &Leaderboard,
+        users: &[SteamId],
+        max_details_len: usize,
+        cb: F,
+    ) where
+        F: FnOnce(Result<Vec<LeaderboardEntry>, SteamError>) + 'static + Send,
+    {
+        // Steam doesn't start the call for more users, so the callback would never run
+        if users.len() > 100 {
+            cb(Err(SteamError::InvalidParameter));
+            return;
+        }
+        unsafe {
+            let mut users: Vec<sys::CSteamID> = users
+                .iter()
+                .map(|user| sys::CSteamID {
+                    m_steamid: sys::CSteamID_SteamID_t {
+                        m_unAll64Bits: user.0,
+                    },
+                })
+                .collect();
+            let api_call = sys::SteamAPI_ISteamUserStats_DownloadLeaderboardEntriesForUsers(
+                self.user_stats,
+                leaderboard.0,
+                users.as_mut_ptr(),
+                users.len() as _,
+            );
+            let user_stats = self.user_stats as isize;
+            register_call_result::<sys::LeaderboardScoresDownloaded_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 5,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else {
+                        Ok(downloaded_entries(user_stats as *mut _, v, max_details_len))
+                    })
+                },
+            );
+        }
+    }
+
+    /// Attaches content shared with `RemoteStorage::share_file`, e.g. a replay, to the
+    /// current user's entry on the leaderboard.
+    ///
+    /// Other users receive the handle with the downloaded entries.
+    pub fn attach_leaderboard_ugc<F>(&self, leaderboard: &Leaderboard, ugc: UGCHandle, cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamUserStats_AttachLeaderboardUGC(
+                self.user_stats,
+                leaderboard.0,
+                ugc.0,
+            );
+            register_call_result::<sys::LeaderboardUGCSet_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 11,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
                     })
                 },
             );
@@ -286,10 +366,13 @@ impl<Manager> UserStats<Manager> {
     }
 
     /// Triggers a [`UserStatsReceived`](./struct.UserStatsReceived.html) callback.
-    pub fn request_current_stats(&self) {
-        unsafe {
-            sys::SteamAPI_ISteamUserStats_RequestCurrentStats(self.user_stats);
-        }
+    ///
+    /// This has to be called, and the callback received, before any stat or achievement
+    /// can be read or written.
+    ///
+    /// Returns false if no user is logged in, in which case no callback will be sent.
+    pub fn request_current_stats(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUserStats_RequestCurrentStats(self.user_stats) }
     }
 
     /// Send the changed stats and achievements data to the server for permanent storage.
@@ -310,6 +393,10 @@ impl<Manager> UserStats<Manager> {
     }
 
     /// Resets the current users stats and, optionally achievements.
+    ///
+    /// The changes are sent to the server straight away, there is no need to call
+    /// [`store_stats()`](#method.store_stats). This is meant for testing during development,
+    /// e.g. from a debug menu.
     pub fn reset_all_stats(&self, achievements_too: bool) -> Result<(), ()> {
         let success = unsafe {
             sys::SteamAPI_ISteamUserStats_ResetAllStats(self.user_stats, achievements_too)
@@ -421,6 +508,79 @@ impl<Manager> UserStats<Manager> {
         }
     }
 
+    /// Updates an AVGRATE stat with new values for the current session
+    ///
+    /// `count_this_session` is the value accumulated during the session, e.g. the points
+    /// scored, and `session_length` is the length of the session in the units of the
+    /// stat's averaging window, e.g. hours for "points per hour".
+    ///
+    /// This call only changes the value in-memory and is very cheap. To commit the stats you
+    /// must call [`store_stats()`](#method.store_stats)
+    ///
+    /// Requires [`request_current_stats()`](#method.request_current_stats) to have been called
+    /// and a successful [`UserStatsReceived`](./struct.UserStatsReceived.html) callback processed.
+    ///
+    /// Fails with `SteamError::InvalidParameter` if the stat is unknown or isn't an AVGRATE
+    /// stat.
+    pub fn update_avg_rate_stat(
+        &self,
+        name: &str,
+        count_this_session: f32,
+        session_length: f64,
+    ) -> Result<(), SteamError> {
+        let name = CString::new(name).map_err(|_| SteamError::InvalidParameter)?;
+
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_UpdateAvgRateStat(
+                self.user_stats,
+                name.as_ptr() as *const _,
+                count_this_session,
+                session_length,
+            )
+        };
+        if success {
+            Ok(())
+        } else {
+            Err(SteamError::InvalidParameter)
+        }
+    }
+
+    /// Returns the number of achievements of the game.
+    ///
+    /// Requires [`request_current_stats()`](#method.request_current_stats) to have been called
+    /// and a successful [`UserStatsReceived`](./struct.UserStatsReceived.html) callback processed.
+    pub fn num_achievements(&self) -> u32 {
+        unsafe { sys::SteamAPI_ISteamUserStats_GetNumAchievements(self.user_stats) }
+    }
+
+    /// Returns the 'API Name' of the achievement at the given index.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub fn achievement_name(&self, index: u32) -> Option<String> {
+        unsafe {
+            let name = sys::SteamAPI_ISteamUserStats_GetAchievementName(self.user_stats, index);
+            if name.is_null() {
+                return None;
+            }
+            let name = CStr::from_ptr(name).to_string_lossy();
+            if name.is_empty() {
+                None
+            } else {
+                Some(name.into_owned())
+            }
+        }
+    }
+
+    /// Returns the 'API Names' of all achievements of the game.
+    ///
+    /// Requires [`request_current_stats()`](#method.request_current_stats) to have been called
+    /// and a successful [`UserStatsReceived`](./struct.UserStatsReceived.html) callback processed.
+    pub fn achievement_names(&self) -> Vec<String> {
+        (0..self.num_achievements())
+            .filter_map(|index| self.achievement_name(index))
+            .collect()
+    }
+
     /// Access achievement API for a given achievement 'API Name'.
     ///
     /// Requires [`request_current_stats()`](#method.request_current_stats) to have been called
@@ -433,55 +593,497 @@ impl<Manager> UserStats<Manager> {
             parent: self,
         }
     }
+
+    /// Downloads the stats and achievements of another user.
+    ///
+    /// Once the callback succeeded they can be read with [`get_user_stat_i32()`](#method.get_user_stat_i32)
+    /// and friends.
+    pub fn request_user_stats<F>(&self, user: SteamId, cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamUserStats_RequestUserStats(self.user_stats, user.0);
+            register_call_result::<sys::UserStatsReceived_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 1,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Gets the value of a given stat for another user
+    ///
+    /// Requires [`request_user_stats()`](#method.request_user_stats) to have succeeded for the user,
+    /// returns `None` otherwise or if the name is unknown.
+    pub fn get_user_stat_i32(&self, user: SteamId, name: &str) -> Option<i32> {
+        let name = CString::new(name).ok()?;
+
+        let mut value: i32 = 0;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetUserStatInt32(
+                self.user_stats,
+                user.0,
+                name.as_ptr() as *const _,
+                &mut value,
+            )
+        };
+        if success {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the value of a given stat for another user
+    ///
+    /// Requires [`request_user_stats()`](#method.request_user_stats) to have succeeded for the user,
+    /// returns `None` otherwise or if the name is unknown.
+    pub fn get_user_stat_f32(&self, user: SteamId, name: &str) -> Option<f32> {
+        let name = CString::new(name).ok()?;
+
+        let mut value: f32 = 0.0;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetUserStatFloat(
+                self.user_stats,
+                user.0,
+                name.as_ptr() as *const _,
+                &mut value,
+            )
+        };
+        if success {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the unlock status of an achievement for another user
+    ///
+    /// Requires [`request_user_stats()`](#method.request_user_stats) to have succeeded for the user,
+    /// returns `None` otherwise or if the name is unknown.
+    pub fn get_user_achievement(&self, user: SteamId, name: &str) -> Option<bool> {
+        let name = CString::new(name).ok()?;
+
+        let mut achieved = false;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetUserAchievement(
+                self.user_stats,
+                user.0,
+                name.as_ptr() as *const _,
+                &mut achieved,
+            )
+        };
+        if success {
+            Some(achieved)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the unlock status of an achievement for another user, along with the time it
+    /// was unlocked in unix epoch seconds
+    ///
+    /// Requires [`request_user_stats()`](#method.request_user_stats) to have succeeded for the user,
+    /// returns `None` otherwise or if the name is unknown.
+    pub fn get_user_achievement_and_unlock_time(
+        &self,
+        user: SteamId,
+        name: &str,
+    ) -> Option<(bool, u32)> {
+        let name = CString::new(name).ok()?;
+
+        let mut achieved = false;
+        let mut unlock_time = 0u32;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetUserAchievementAndUnlockTime(
+                self.user_stats,
+                user.0,
+                name.as_ptr() as *const _,
+                &mut achieved,
+                &mut unlock_time,
+            )
+        };
+        if success {
+            Some((achieved, unlock_time))
+        } else {
+            None
+        }
+    }
+
+    /// Downloads the global stats of the game, with up to `history_days` days of history
+    /// (capped at 60).
+    ///
+    /// Once the callback succeeded they can be read with
+    /// [`get_global_stat_i64()`](#method.get_global_stat_i64) and friends. Only stats
+    /// marked as aggregated on the Steamworks App Admin website are available.
+    pub fn request_global_stats<F>(&self, history_days: i32, cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call =
+                sys::SteamAPI_ISteamUserStats_RequestGlobalStats(self.user_stats, history_days);
+            register_call_result::<sys::GlobalStatsReceived_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 12,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Gets the global total of an aggregated integer stat
+    ///
+    /// Requires [`request_global_stats()`](#method.request_global_stats) to have succeeded,
+    /// returns `None` otherwise or if the name is unknown.
+    pub fn get_global_stat_i64(&self, name: &str) -> Option<i64> {
+        let name = CString::new(name).ok()?;
+
+        let mut value: i64 = 0;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetGlobalStatInt64(
+                self.user_stats,
+                name.as_ptr() as *const _,
+                &mut value,
+            )
+        };
+        if success {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the global total of an aggregated float stat
+    ///
+    /// Requires [`request_global_stats()`](#method.request_global_stats) to have succeeded,
+    /// returns `None` otherwise or if the name is unknown.
+    pub fn get_global_stat_f64(&self, name: &str) -> Option<f64> {
+        let name = CString::new(name).ok()?;
+
+        let mut value: f64 = 0.0;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetGlobalStatDouble(
+                self.user_stats,
+                name.as_ptr() as *const _,
+                &mut value,
+            )
+        };
+        if success {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the daily history of an aggregated integer stat, starting with today
+    ///
+    /// At most `days` values are returned, fewer if less history was requested with
+    /// [`request_global_stats()`](#method.request_global_stats). Returns `None` if
+    /// the global stats weren't received or the name is unknown.
+    pub fn get_global_stat_history_i64(&self, name: &str, days: usize) -> Option<Vec<i64>> {
+        let name = CString::new(name).ok()?;
+
+        let mut history = vec![0i64; days];
+        let len = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetGlobalStatHistoryInt64(
+                self.user_stats,
+                name.as_ptr() as *const _,
+                history.as_mut_ptr(),
+                (history.len() * std::mem::size_of::<i64>()) as _,
+            )
+        };
+        if len <= 0 {
+            return None;
+        }
+        history.truncate(len as usize);
+        Some(history)
+    }
+
+    /// Gets the daily history of an aggregated float stat, starting with today
+    ///
+    /// At most `days` values are returned, fewer if less history was requested with
+    /// [`request_global_stats()`](#method.request_global_stats). Returns `None` if
+    /// the global stats weren't received or the name is unknown.
+    pub fn get_global_stat_history_f64(&self, name: &str, days: usize) -> Option<Vec<f64>> {
+        let name = CString::new(name).ok()?;
+
+        let mut history = vec![0f64; days];
+        let len = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetGlobalStatHistoryDouble(
+                self.user_stats,
+                name.as_ptr() as *const _,
+                history.as_mut_ptr(),
+                (history.len() * std::mem::size_of::<f64>()) as _,
+            )
+        };
+        if len <= 0 {
+            return None;
+        }
+        history.truncate(len as usize);
+        Some(history)
+    }
+
+    /// Returns a helper that downloads the entries of the leaderboard one page at a time,
+    /// e.g. for an infinitely scrolling leaderboard.
+    ///
+    /// Global pages start at the first rank and pages around the user start at the user's
+    /// own entry. The entries of friends are always returned as a single page.
+    pub fn leaderboard_pages(
+        &self,
+        leaderboard: &Leaderboard,
+        request: LeaderboardDataRequest,
+        page_size: usize,
+        max_details_len: usize,
+    ) -> LeaderboardPages<Manager> {
+        assert!(page_size > 0, "page size must not be zero");
+        LeaderboardPages {
+            stats: UserStats {
+                user_stats: self.user_stats,
+                inner: self.inner.clone(),
+            },
+            leaderboard: leaderboard.clone(),
+            request,
+            page_size,
+            max_details_len,
+            state: Arc::new(Mutex::new(LeaderboardPagesState {
+                next_page: 0,
+                loading: false,
+                exhausted: false,
+            })),
+        }
+    }
+
+    /// Gets the number of players currently playing the game, online or offline.
+    pub fn get_number_of_current_players<F>(&self, cb: F)
+    where
+        F: FnOnce(Result<i32, SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call = sys::SteamAPI_ISteamUserStats_GetNumberOfCurrentPlayers(self.user_stats);
+            register_call_result::<sys::NumberOfCurrentPlayers_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 7,
+                move |v, io_error| {
+                    cb(if io_error || v.m_bSuccess == 0 {
+                        Err(SteamError::IOFailure)
+                    } else {
+                        Ok(v.m_cPlayers)
+                    })
+                },
+            );
+        }
+    }
+
+    /// Downloads the percentage of players that unlocked each achievement.
+    ///
+    /// Once the callback succeeded they can be read with
+    /// [`achieved_achievement_percentages()`](#method.achieved_achievement_percentages) or
+    /// `AchievementHelper::achieved_percent`.
+    pub fn request_global_achievement_percentages<F>(&self, cb: F)
+    where
+        F: FnOnce(Result<(), SteamError>) + 'static + Send,
+    {
+        unsafe {
+            let api_call =
+                sys::SteamAPI_ISteamUserStats_RequestGlobalAchievementPercentages(self.user_stats);
+            register_call_result::<sys::GlobalAchievementPercentagesReady_t, _, _>(
+                &self.inner,
+                api_call,
+                CALLBACK_BASE_ID + 10,
+                move |v, io_error| {
+                    cb(if io_error {
+                        Err(SteamError::IOFailure)
+                    } else if v.m_eResult != sys::EResult::k_EResultOK {
+                        Err(v.m_eResult.into())
+                    } else {
+                        Ok(())
+                    })
+                },
+            );
+        }
+    }
+
+    /// Returns all achievements, sorted from the most to the least unlocked.
+    ///
+    /// Requires [`request_global_achievement_percentages()`](#method.request_global_achievement_percentages)
+    /// to have succeeded.
+    pub fn achieved_achievement_percentages(&self) -> Vec<AchievementPercentage> {
+        let mut achievements = Vec::new();
+        unsafe {
+            let mut name = [0 as std::os::raw::c_char; sys::k_cchStatNameMax as usize];
+            let mut percent = 0.0;
+            let mut achieved = false;
+            let mut iterator = sys::SteamAPI_ISteamUserStats_GetMostAchievedAchievementInfo(
+                self.user_stats,
+                name.as_mut_ptr(),
+                name.len() as _,
+                &mut percent,
+                &mut achieved,
+            );
+            while iterator != -1 {
+                achievements.push(AchievementPercentage {
+                    name: CStr::from_ptr(name.as_ptr()).to_string_lossy().into_owned(),
+                    percent,
+                    achieved,
+                });
+                iterator = sys::SteamAPI_ISteamUserStats_GetNextMostAchievedAchievementInfo(
+                    self.user_stats,
+                    iterator,
+                    name.as_mut_ptr(),
+                    name.len() as _,
+                    &mut percent,
+                    &mut achieved,
+                );
+            }
+        }
+        achievements
+    }
+}
+
+unsafe fn downloaded_entries(
+    user_stats: *mut sys::ISteamUserStats,
+    v: &sys::LeaderboardScoresDownloaded_t,
+    max_details_len: usize,
+) -> Vec<LeaderboardEntry> {
+    let len = v.m_cEntryCount;
+    let mut entries = Vec::with_capacity(len as usize);
+    for idx in 0..len {
+        let mut entry: sys::LeaderboardEntry_t = std::mem::zeroed();
+        let mut details = Vec::with_capacity(max_details_len);
+
+        sys::SteamAPI_ISteamUserStats_GetDownloadedLeaderboardEntry(
+            user_stats,
+            v.m_hSteamLeaderboardEntries,
+            idx,
+            &mut entry,
+            details.as_mut_ptr(),
+            max_details_len as _,
+        );
+        details.set_len((entry.m_cDetails as usize).min(max_details_len));
+
+        entries.push(LeaderboardEntry {
+            user: SteamId(entry.m_steamIDUser.m_steamid.m_unAll64Bits),
+            global_rank: entry.m_nGlobalRank,
+            score: entry.m_nScore,
+            details,
+            ugc: if entry.m_hUGC == sys::k_UGCHandleInvalid {
+                None
+            } else {
+                Some(UGCHandle(entry.m_hUGC))
+            },
+        })
+    }
+    entries
+}
+
+/// How many players unlocked an achievement, see `UserStats::achieved_achievement_percentages`
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AchievementPercentage {
+    /// The 'API Name' of the achievement
+    pub name: String,
+    /// The percentage of players that unlocked the achievement, between 0 and 100
+    pub percent: f32,
+    /// Whether the current user unlocked the achievement
+    pub achieved: bool,
 }
 
+/// An entry of a leaderboard, see `UserStats::download_leaderboard_entries`
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LeaderboardEntry {
+    /// The user the entry belongs to
     pub user: SteamId,
+    /// The global rank of the entry, starting at 1
     pub global_rank: i32,
+    /// The score of the entry
     pub score: i32,
+    /// The extra details uploaded with the score
     pub details: Vec<i32>,
+    /// Content attached to the entry, e.g. a replay, which can be downloaded
+    /// with `RemoteStorage::download_ugc`
+    pub ugc: Option<UGCHandle>,
 }
 
+/// Which entries of a leaderboard to download
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LeaderboardDataRequest {
+    /// Entries by global rank, the range is the ranks to download
     Global,
+    /// Entries around the current user, the range is relative to the user's rank
     GlobalAroundUser,
+    /// Entries of the current user's friends, the range is ignored
     Friends,
 }
 
+/// The result of `UserStats::upload_leaderboard_score`
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LeaderboardScoreUploaded {
+    /// The score that was uploaded
     pub score: i32,
+    /// Whether the score on the leaderboard was changed
     pub was_changed: bool,
+    /// The new global rank of the user, 0 if the user had no entry
     pub global_rank_new: i32,
+    /// The previous global rank of the user, 0 if the user had no entry
     pub global_rank_previous: i32,
 }
 
-#[derive(Clone, Debug)]
+/// How an uploaded score replaces the existing score of the user
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UploadScoreMethod {
+    /// Only replaces the existing score if the new score is better
     KeepBest,
+    /// Always replaces the existing score
     ForceUpdate,
 }
 
-#[derive(Clone, Debug)]
+/// How the entries of a leaderboard are ranked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LeaderboardSortMethod {
+    /// The lowest score is ranked first
     Ascending,
+    /// The highest score is ranked first
     Descending,
 }
 
-#[derive(Clone, Debug)]
+/// How the scores of a leaderboard are displayed on the Steam Community site
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LeaderboardDisplayType {
+    /// The score is a plain number
     Numeric,
+    /// The score is a time in seconds
     TimeSeconds,
+    /// The score is a time in milliseconds
     TimeMilliSeconds,
 }
 
-#[derive(Clone, Debug)]
+/// A handle to a leaderboard, see `UserStats::find_leaderboard`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Leaderboard(u64);
 
@@ -495,6 +1097,98 @@ impl Leaderboard {
     }
 }
 
+/// Downloads the entries of a leaderboard page by page, see `UserStats::leaderboard_pages`
+pub struct LeaderboardPages<Manager> {
+    stats: UserStats<Manager>,
+    leaderboard: Leaderboard,
+    request: LeaderboardDataRequest,
+    page_size: usize,
+    max_details_len: usize,
+    state: Arc<Mutex<LeaderboardPagesState>>,
+}
+
+struct LeaderboardPagesState {
+    next_page: usize,
+    loading: bool,
+    exhausted: bool,
+}
+
+impl<Manager> LeaderboardPages<Manager> {
+    /// Returns whether there may be more entries to download
+    pub fn has_more(&self) -> bool {
+        !self.state.lock().unwrap().exhausted
+    }
+
+    /// Returns whether a page is currently being downloaded
+    pub fn is_loading(&self) -> bool {
+        self.state.lock().unwrap().loading
+    }
+
+    /// Starts downloading the next page.
+    ///
+    /// If the download fails the same page is downloaded by the next call. Returns
+    /// false, without calling the callback, if a page is already being downloaded or
+    /// there are no more entries.
+    pub fn next_page<F>(&self, cb: F) -> bool
+    where
+        F: FnOnce(Result<Vec<LeaderboardEntry>, SteamError>) + 'static + Send,
+    {
+        let page = {
+            let mut state = self.state.lock().unwrap();
+            if state.loading || state.exhausted {
+                return false;
+            }
+            state.loading = true;
+            state.next_page
+        };
+        let (start, end) = match self.request {
+            LeaderboardDataRequest::Global => {
+                (page * self.page_size + 1, (page + 1) * self.page_size)
+            }
+            LeaderboardDataRequest::GlobalAroundUser | LeaderboardDataRequest::Friends => {
+                (page * self.page_size, (page + 1) * self.page_size - 1)
+            }
+        };
+        let state = self.state.clone();
+        let page_size = self.page_size;
+        let single_page = self.request == LeaderboardDataRequest::Friends;
+        self.stats.download_leaderboard_entries(
+            &self.leaderboard,
+            self.request,
+            start,
+            end,
+            self.max_details_len,
+            move |entries| {
+                {
+                    let mut state = state.lock().unwrap();
+                    state.loading = false;
+                    if let Ok(entries) = &entries {
+                        state.next_page += 1;
+                        state.exhausted = single_page || entries.len() < page_size;
+                    }
+                }
+                cb(entries)
+            },
+        );
+        true
+    }
+
+    /// Like `next_page`, but returns a future instead of taking a callback
+    ///
+    /// Returns `None` if a page is already being downloaded or there are no more entries.
+    #[cfg(feature = "futures")]
+    pub fn next_page_future(
+        &self,
+    ) -> Option<CallResultFuture<Result<Vec<LeaderboardEntry>, SteamError>>> {
+        let (future, complete) = CallResultFuture::new();
+        if self.next_page(complete) {
+            Some(future)
+        } else {
+            None
+        }
+    }
+}
+
 #[test]
 #[serial]
 fn test() {
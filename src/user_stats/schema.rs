@@ -0,0 +1,291 @@
+use super::*;
+
+use std::time::{Duration, Instant};
+
+/// A type holding a set of stats and achievements, usually generated with
+/// [`steam_stats!`](../macro.steam_stats.html).
+pub trait StatsSchema: Clone + PartialEq {
+    /// Reads every stat and achievement of the current user.
+    ///
+    /// Values that couldn't be read are left at their defaults.
+    fn load<M>(stats: &UserStats<M>) -> Self;
+
+    /// Writes every value that differs from `synced`, copying the ones steam accepted
+    /// into `synced`.
+    ///
+    /// Values that couldn't be written are left unchanged in `synced`, so they are
+    /// written again by the next call.
+    fn write_changes<M>(&self, synced: &mut Self, stats: &UserStats<M>) -> WrittenChanges;
+}
+
+/// The outcome of `StatsSchema::write_changes`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WrittenChanges {
+    /// Whether any value was written
+    pub written: bool,
+    /// The 'API Names' of the values steam refused to write, e.g. because the name is
+    /// unknown or the stats weren't received yet
+    pub failed: Vec<&'static str>,
+}
+
+/// Defines a struct with a field for each of the given stats and achievements.
+///
+/// The fields are named after the 'API Names' of the stats and achievements. Stats may
+/// be `i32` or `f32`, achievements are `bool`. The struct implements `StatsSchema`, so
+/// it can be used with a `StatsTracker` that writes changed values and batches the
+/// calls to `store_stats`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use steamworks::*;
+/// # use std::time::Duration;
+/// steam_stats! {
+///     pub struct GameStats {
+///         stats {
+///             kills: i32,
+///             playtime: f32,
+///         }
+///         achievements {
+///             ACH_FIRST_BLOOD,
+///         }
+///     }
+/// }
+///
+/// # let (client, single) = steamworks::Client::init().unwrap();
+/// // After the `UserStatsReceived` callback
+/// let mut stats = StatsTracker::<GameStats>::load(&client.user_stats(), Duration::from_secs(60));
+/// stats.kills += 1;
+/// stats.ACH_FIRST_BLOOD = true;
+///
+/// // Every frame
+/// stats.sync(&client.user_stats());
+/// ```
+#[macro_export]
+macro_rules! steam_stats {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            stats {
+                $($stat:ident: $ty:ty),* $(,)?
+            }
+            $(achievements {
+                $($achievement:ident),* $(,)?
+            })?
+        }
+    ) => {
+        $(#[$attr])*
+        #[allow(non_snake_case)]
+        #[derive(Clone, Debug, Default, PartialEq)]
+        $vis struct $name {
+            $(pub $stat: $ty,)*
+            $($(pub $achievement: bool,)*)?
+        }
+
+        impl $crate::StatsSchema for $name {
+            fn load<M>(stats: &$crate::UserStats<M>) -> Self {
+                let mut value = Self::default();
+                $(
+                    if let Some(stat) =
+                        <$ty as $crate::StatValue>::get(stats, stringify!($stat))
+                    {
+                        value.$stat = stat;
+                    }
+                )*
+                $($(
+                    if let Ok(achieved) = stats.achievement(stringify!($achievement)).get() {
+                        value.$achievement = achieved;
+                    }
+                )*)?
+                value
+            }
+
+            fn write_changes<M>(
+                &self,
+                synced: &mut Self,
+                stats: &$crate::UserStats<M>,
+            ) -> $crate::WrittenChanges {
+                let mut changes = $crate::WrittenChanges::default();
+                $(
+                    if self.$stat != synced.$stat {
+                        if $crate::StatValue::set(self.$stat, stats, stringify!($stat)) {
+                            synced.$stat = self.$stat;
+                            changes.written = true;
+                        } else {
+                            changes.failed.push(stringify!($stat));
+                        }
+                    }
+                )*
+                $($(
+                    if self.$achievement != synced.$achievement {
+                        let achievement = stats.achievement(stringify!($achievement));
+                        let result = if self.$achievement {
+                            achievement.set()
+                        } else {
+                            achievement.clear()
+                        };
+                        if result.is_ok() {
+                            synced.$achievement = self.$achievement;
+                            changes.written = true;
+                        } else {
+                            changes.failed.push(stringify!($achievement));
+                        }
+                    }
+                )*)?
+                changes
+            }
+        }
+    };
+}
+
+/// A type that can be stored in a steam stat
+pub trait StatValue: Copy + PartialEq {
+    /// Reads the stat of the current user
+    fn get<M>(stats: &UserStats<M>, name: &str) -> Option<Self>;
+    /// Writes the stat of the current user, returning whether it succeeded
+    fn set<M>(self, stats: &UserStats<M>, name: &str) -> bool;
+}
+
+impl StatValue for i32 {
+    fn get<M>(stats: &UserStats<M>, name: &str) -> Option<Self> {
+        stats.get_stat_i32(name).ok()
+    }
+
+    fn set<M>(self, stats: &UserStats<M>, name: &str) -> bool {
+        stats.set_stat_i32(name, self).is_ok()
+    }
+}
+
+impl StatValue for f32 {
+    fn get<M>(stats: &UserStats<M>, name: &str) -> Option<Self> {
+        stats.get_stat_f32(name).ok()
+    }
+
+    fn set<M>(self, stats: &UserStats<M>, name: &str) -> bool {
+        stats.set_stat_f32(name, self).is_ok()
+    }
+}
+
+/// Keeps a `StatsSchema` in sync with steam.
+///
+/// Changes made through `DerefMut` are written to steam by `sync`, which also calls
+/// `store_stats` at most once per store interval so that frequent updates don't spam
+/// the servers.
+pub struct StatsTracker<T> {
+    current: T,
+    synced: T,
+    store_interval: Duration,
+    last_store: Option<Instant>,
+    unstored: bool,
+    failed: Vec<&'static str>,
+}
+
+impl<T: StatsSchema> StatsTracker<T> {
+    /// Reads the current values from steam.
+    ///
+    /// Requires [`request_current_stats()`](struct.UserStats.html#method.request_current_stats)
+    /// to have been called and a successful [`UserStatsReceived`](struct.UserStatsReceived.html)
+    /// callback processed.
+    pub fn load<M>(stats: &UserStats<M>, store_interval: Duration) -> Self {
+        let current = T::load(stats);
+        StatsTracker {
+            synced: current.clone(),
+            current,
+            store_interval,
+            last_store: None,
+            unstored: false,
+            failed: Vec::new(),
+        }
+    }
+
+    /// Returns whether there are changes that weren't stored on the server yet
+    pub fn is_dirty(&self) -> bool {
+        self.unstored || self.current != self.synced
+    }
+
+    /// The 'API Names' of the values the last sync couldn't write to steam
+    ///
+    /// They stay dirty and are written again by the next sync.
+    pub fn failed_writes(&self) -> &[&'static str] {
+        &self.failed
+    }
+
+    /// Writes the changed values to steam, storing them on the server if the store
+    /// interval elapsed.
+    ///
+    /// Returns whether the stats were stored.
+    pub fn sync<M>(&mut self, stats: &UserStats<M>) -> bool {
+        self.write_changes(stats);
+        let interval_elapsed = match self.last_store {
+            Some(last_store) => last_store.elapsed() >= self.store_interval,
+            None => true,
+        };
+        if self.unstored && interval_elapsed {
+            self.store(stats)
+        } else {
+            false
+        }
+    }
+
+    /// Writes the changed values to steam and stores them on the server straight away,
+    /// e.g. when the game is closed.
+    ///
+    /// Returns whether the stats were stored.
+    pub fn sync_now<M>(&mut self, stats: &UserStats<M>) -> bool {
+        self.write_changes(stats);
+        self.unstored && self.store(stats)
+    }
+
+    fn write_changes<M>(&mut self, stats: &UserStats<M>) {
+        let changes = self.current.write_changes(&mut self.synced, stats);
+        if changes.written {
+            self.unstored = true;
+        }
+        self.failed = changes.failed;
+    }
+
+    fn store<M>(&mut self, stats: &UserStats<M>) -> bool {
+        self.last_store = Some(Instant::now());
+        if stats.store_stats().is_ok() {
+            self.unstored = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<T> std::ops::Deref for StatsTracker<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.current
+    }
+}
+
+impl<T> std::ops::DerefMut for StatsTracker<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.current
+    }
+}
+
+#[test]
+#[serial]
+fn test_failed_write() {
+    steam_stats! {
+        struct TestStats {
+            stats {
+                steamworks_test_unknown_stat: i32,
+            }
+        }
+    }
+
+    let (client, _single) = Client::init().unwrap();
+    let stats = client.user_stats();
+    let mut tracker = StatsTracker::<TestStats>::load(&stats, Duration::from_secs(60));
+    tracker.steamworks_test_unknown_stat += 1;
+
+    assert!(!tracker.sync_now(&stats));
+    assert_eq!(tracker.failed_writes(), &["steamworks_test_unknown_stat"]);
+    assert!(tracker.is_dirty());
+}
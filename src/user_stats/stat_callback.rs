@@ -14,10 +14,13 @@ use super::*;
 ///     }
 /// });
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct UserStatsReceived {
+    /// The user whose stats were received
     pub steam_id: SteamId,
+    /// The game the stats are for
     pub game_id: GameId,
+    /// Whether the stats were received successfully
     pub result: Result<(), SteamError>,
 }
 
@@ -38,7 +41,7 @@ unsafe impl Callback for UserStatsReceived {
     }
 }
 
-/// Callback triggered by [`store()`](stats/struct.StatsHelper.html#method.store).
+/// Callback triggered by [`store_stats()`](struct.UserStats.html#method.store_stats).
 ///
 /// # Example
 ///
@@ -51,9 +54,11 @@ unsafe impl Callback for UserStatsReceived {
 ///     }
 /// });
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct UserStatsStored {
+    /// The game the stats were stored for
     pub game_id: GameId,
+    /// Whether the stats were stored successfully
     pub result: Result<(), SteamError>,
 }
 
@@ -86,9 +91,11 @@ unsafe impl Callback for UserStatsStored {
 ///     // ...
 /// });
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct UserAchievementStored {
+    /// The game the achievement belongs to
     pub game_id: GameId,
+    /// The 'API Name' of the achievement
     pub achievement_name: String,
     /// Current progress towards the achievement.
     pub current_progress: u32,
@@ -102,10 +109,10 @@ unsafe impl Callback for UserAchievementStored {
 
     unsafe fn from_raw(raw: *mut c_void) -> Self {
         let val = &mut *(raw as *mut sys::UserAchievementStored_t);
-        let name = CStr::from_ptr(val.m_rgchAchievementName.as_ptr()).to_owned();
+        let name = CStr::from_ptr(val.m_rgchAchievementName.as_ptr());
         Self {
             game_id: GameId(val.m_nGameID),
-            achievement_name: name.into_string().unwrap(),
+            achievement_name: name.to_string_lossy().into_owned(),
             current_progress: val.m_nCurProgress,
             max_progress: val.m_nMaxProgress,
         }
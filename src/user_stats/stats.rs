@@ -46,6 +46,91 @@ impl<M> AchievementHelper<'_, M> {
         }
     }
 
+    /// Gets the unlock status of the Achievement, along with the time it was unlocked.
+    ///
+    /// The time is in unix epoch seconds, and is 0 if the achievement isn't unlocked.
+    ///
+    /// Returns `None` if this achievement's 'API Name' is unknown, or unsuccessful
+    /// [`UserStatsReceived`](../struct.UserStatsReceived.html).
+    pub fn get_with_unlock_time(&self) -> Option<(bool, u32)> {
+        unsafe {
+            let mut achieved = false;
+            let mut unlock_time = 0u32;
+            let success = sys::SteamAPI_ISteamUserStats_GetAchievementAndUnlockTime(
+                self.parent.user_stats,
+                self.name.as_ptr() as *const _,
+                &mut achieved as *mut _,
+                &mut unlock_time as *mut _,
+            );
+            if success {
+                Some((achieved, unlock_time))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Gets a display attribute of the achievement, localized to the current game language.
+    ///
+    /// The available keys are `"name"`, `"desc"` and `"hidden"`, where `"hidden"` is `"0"`
+    /// or `"1"`.
+    ///
+    /// Returns `None` if the attribute or the achievement doesn't exist.
+    pub fn display_attribute(&self, key: &str) -> Option<String> {
+        let key = CString::new(key).unwrap();
+        unsafe {
+            let value = sys::SteamAPI_ISteamUserStats_GetAchievementDisplayAttribute(
+                self.parent.user_stats,
+                self.name.as_ptr() as *const _,
+                key.as_ptr() as *const _,
+            );
+            if value.is_null() {
+                return None;
+            }
+            let value = CStr::from_ptr(value).to_string_lossy();
+            if value.is_empty() {
+                None
+            } else {
+                Some(value.into_owned())
+            }
+        }
+    }
+
+    /// Gets the localized display name of the achievement.
+    pub fn display_name(&self) -> Option<String> {
+        self.display_attribute("name")
+    }
+
+    /// Gets the localized description of the achievement.
+    pub fn description(&self) -> Option<String> {
+        self.display_attribute("desc")
+    }
+
+    /// Gets whether the achievement is hidden until it's unlocked.
+    pub fn hidden(&self) -> Option<bool> {
+        self.display_attribute("hidden").map(|hidden| hidden == "1")
+    }
+
+    /// Gets the percentage of players that unlocked the achievement, between 0 and 100.
+    ///
+    /// Requires [`request_global_achievement_percentages()`](../struct.UserStats.html#method.request_global_achievement_percentages)
+    /// to have succeeded.
+    pub fn achieved_percent(&self) -> Option<f32> {
+        let mut percent = 0.0;
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_GetAchievementAchievedPercent(
+                self.parent.user_stats,
+                self.name.as_ptr() as *const _,
+                &mut percent,
+            )
+        };
+        if success {
+            Some(percent)
+        } else {
+            None
+        }
+    }
+
     /// Unlocks an achievement.
     ///
     /// This call only modifies Steam's in-memory state so it is quite cheap. To send the unlock
@@ -68,6 +153,34 @@ impl<M> AchievementHelper<'_, M> {
         }
     }
 
+    /// Shows the user a pop-up notification with the current progress of the achievement.
+    ///
+    /// This doesn't unlock the achievement, use [`set()`](#method.set) for that. A
+    /// [`UserAchievementStored`](../struct.UserAchievementStored.html) callback is posted on
+    /// success.
+    ///
+    /// Fails with `SteamError::InvalidParameter` if this achievement's 'API Name' is unknown,
+    /// the achievement is already unlocked, or `current_progress` is not below `max_progress`.
+    pub fn indicate_progress(
+        &self,
+        current_progress: u32,
+        max_progress: u32,
+    ) -> Result<(), SteamError> {
+        let success = unsafe {
+            sys::SteamAPI_ISteamUserStats_IndicateAchievementProgress(
+                self.parent.user_stats,
+                self.name.as_ptr() as *const _,
+                current_progress,
+                max_progress,
+            )
+        };
+        if success {
+            Ok(())
+        } else {
+            Err(SteamError::InvalidParameter)
+        }
+    }
+
     /// Resets the unlock status of an achievement.
     ///
     /// This call only modifies Steam's in-memory state so it is quite cheap. To send the unlock
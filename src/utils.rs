@@ -5,6 +5,9 @@ use std::os::raw::c_char;
 use std::panic;
 use std::process::abort;
 use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+const CALLBACK_BASE_ID: i32 = 700;
 
 /// Access to the steam utils interface
 pub struct Utils<Manager> {
@@ -12,6 +15,9 @@ pub struct Utils<Manager> {
     pub(crate) _inner: Arc<Inner<Manager>>,
 }
 
+/// The corner of the screen overlay notifications are shown in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NotificationPosition {
     TopLeft,
     TopRight,
@@ -19,6 +25,185 @@ pub enum NotificationPosition {
     BottomRight,
 }
 
+/// The kind of text a gamepad text input dialog accepts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadTextInputMode {
+    /// Regular text
+    Normal,
+    /// Text that is hidden while being typed
+    Password,
+}
+
+impl From<GamepadTextInputMode> for sys::EGamepadTextInputMode {
+    fn from(mode: GamepadTextInputMode) -> Self {
+        match mode {
+            GamepadTextInputMode::Normal => {
+                sys::EGamepadTextInputMode::k_EGamepadTextInputModeNormal
+            }
+            GamepadTextInputMode::Password => {
+                sys::EGamepadTextInputMode::k_EGamepadTextInputModePassword
+            }
+        }
+    }
+}
+
+/// Whether a gamepad text input dialog accepts multiple lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadTextInputLineMode {
+    SingleLine,
+    MultipleLines,
+}
+
+impl From<GamepadTextInputLineMode> for sys::EGamepadTextInputLineMode {
+    fn from(mode: GamepadTextInputLineMode) -> Self {
+        match mode {
+            GamepadTextInputLineMode::SingleLine => {
+                sys::EGamepadTextInputLineMode::k_EGamepadTextInputLineModeSingleLine
+            }
+            GamepadTextInputLineMode::MultipleLines => {
+                sys::EGamepadTextInputLineMode::k_EGamepadTextInputLineModeMultipleLines
+            }
+        }
+    }
+}
+
+/// The kind of keyboard a floating gamepad text input shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FloatingGamepadTextInputMode {
+    /// Enter closes the keyboard
+    SingleLine,
+    /// Enter inserts a new line
+    MultipleLines,
+    /// A keyboard layout for email addresses
+    Email,
+    /// A numeric keypad
+    Numeric,
+}
+
+impl From<FloatingGamepadTextInputMode> for sys::EFloatingGamepadTextInputMode {
+    fn from(mode: FloatingGamepadTextInputMode) -> Self {
+        match mode {
+            FloatingGamepadTextInputMode::SingleLine => {
+                sys::EFloatingGamepadTextInputMode::k_EFloatingGamepadTextInputModeModeSingleLine
+            }
+            FloatingGamepadTextInputMode::MultipleLines => {
+                sys::EFloatingGamepadTextInputMode::k_EFloatingGamepadTextInputModeModeMultipleLines
+            }
+            FloatingGamepadTextInputMode::Email => {
+                sys::EFloatingGamepadTextInputMode::k_EFloatingGamepadTextInputModeModeEmail
+            }
+            FloatingGamepadTextInputMode::Numeric => {
+                sys::EFloatingGamepadTextInputMode::k_EFloatingGamepadTextInputModeModeNumeric
+            }
+        }
+    }
+}
+
+/// Where text passed to `Utils::filter_text` comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextFilteringContext {
+    /// Text of an unknown origin
+    Unknown,
+    /// Content created by players, e.g. item or level names
+    GameContent,
+    /// Chat messages from other players
+    Chat,
+    /// Character or item names chosen by players
+    Name,
+}
+
+impl From<TextFilteringContext> for sys::ETextFilteringContext {
+    fn from(context: TextFilteringContext) -> Self {
+        match context {
+            TextFilteringContext::Unknown => {
+                sys::ETextFilteringContext::k_ETextFilteringContextUnknown
+            }
+            TextFilteringContext::GameContent => {
+                sys::ETextFilteringContext::k_ETextFilteringContextGameContent
+            }
+            TextFilteringContext::Chat => sys::ETextFilteringContext::k_ETextFilteringContextChat,
+            TextFilteringContext::Name => sys::ETextFilteringContext::k_ETextFilteringContextName,
+        }
+    }
+}
+
+/// A handle to an asynchronous steam api call, a `SteamAPICall_t`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ApiCall(pub u64);
+
+/// Why an asynchronous steam api call failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum ApiCallFailure {
+    /// The connection to steam was lost
+    #[error("steam went away")]
+    SteamGone,
+    /// The network connection to the steam servers was lost
+    #[error("network failure")]
+    NetworkFailure,
+    /// The handle doesn't belong to a call, or the result was already read
+    #[error("invalid api call handle")]
+    InvalidHandle,
+    /// The result is of a different type than the one requested
+    #[error("mismatched callback type")]
+    MismatchedCallback,
+}
+
+impl ApiCallFailure {
+    fn from_raw(failure: sys::ESteamAPICallFailure) -> Option<ApiCallFailure> {
+        match failure {
+            sys::ESteamAPICallFailure::k_ESteamAPICallFailureSteamGone => {
+                Some(ApiCallFailure::SteamGone)
+            }
+            sys::ESteamAPICallFailure::k_ESteamAPICallFailureNetworkFailure => {
+                Some(ApiCallFailure::NetworkFailure)
+            }
+            sys::ESteamAPICallFailure::k_ESteamAPICallFailureInvalidHandle => {
+                Some(ApiCallFailure::InvalidHandle)
+            }
+            sys::ESteamAPICallFailure::k_ESteamAPICallFailureMismatchedCallback => {
+                Some(ApiCallFailure::MismatchedCallback)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An image loaded from steam, e.g. an avatar or achievement icon
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    /// The width of the image in pixels
+    pub width: u32,
+    /// The height of the image in pixels
+    pub height: u32,
+    /// The pixels of the image, 4 bytes per pixel in RGBA order
+    pub rgba: Vec<u8>,
+}
+
+/// Loads the image behind an image handle returned by one of the steam interfaces
+pub(crate) unsafe fn load_image(utils: *mut sys::ISteamUtils, handle: i32) -> Option<Image> {
+    if handle == 0 {
+        return None;
+    }
+    let mut width = 0;
+    let mut height = 0;
+    if !sys::SteamAPI_ISteamUtils_GetImageSize(utils, handle, &mut width, &mut height) {
+        return None;
+    }
+    let mut rgba = vec![0; width as usize * height as usize * 4];
+    if !sys::SteamAPI_ISteamUtils_GetImageRGBA(utils, handle, rgba.as_mut_ptr(), rgba.len() as _) {
+        return None;
+    }
+    Some(Image {
+        width,
+        height,
+        rgba,
+    })
+}
+
 lazy_static! {
     /// Global rust warning callback
     static ref WARNING_CALLBACK: RwLock<Option<Box<dyn Fn(i32, &CStr) + Send + Sync>>> = RwLock::new(None);
@@ -68,6 +253,7 @@ impl<Manager> Utils<Manager> {
     /// running in.
     ///
     /// Generally you want `Apps::current_game_language` instead of this
+    #[doc(alias = "steam_ui_language")]
     pub fn ui_language(&self) -> String {
         unsafe {
             let lang = sys::SteamAPI_ISteamUtils_GetSteamUILanguage(self.utils);
@@ -82,6 +268,185 @@ impl<Manager> Utils<Manager> {
         unsafe { sys::SteamAPI_ISteamUtils_GetServerRealTime(self.utils) }
     }
 
+    /// Returns the current real time on the Steam servers.
+    ///
+    /// Unlike the local clock this can't be changed by the user, which
+    /// makes it suitable for timestamps that must be trusted.
+    pub fn server_real_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.get_server_real_time() as u64)
+    }
+
+    /// Returns the image behind the given image handle.
+    ///
+    /// Avatars, achievement icons and inventory icons are returned as image
+    /// handles. Returns `None` if the handle is invalid or the image hasn't
+    /// been loaded yet.
+    pub fn image(&self, handle: i32) -> Option<Image> {
+        unsafe { load_image(self.utils, handle) }
+    }
+
+    /// Returns whether the game is running on a Steam Deck
+    pub fn is_steam_running_on_steam_deck(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_IsSteamRunningOnSteamDeck(self.utils) }
+    }
+
+    /// Returns whether steam is running in Big Picture mode
+    pub fn is_steam_in_big_picture_mode(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_IsSteamInBigPictureMode(self.utils) }
+    }
+
+    /// Returns whether the game was started by the Steam China launcher
+    pub fn is_steam_china_launcher(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_IsSteamChinaLauncher(self.utils) }
+    }
+
+    /// Returns whether steam is running in VR mode
+    pub fn is_steam_running_in_vr(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_IsSteamRunningInVR(self.utils) }
+    }
+
+    /// Opens the SteamVR dashboard if steam is running in VR mode
+    pub fn start_vr_dashboard(&self) {
+        unsafe { sys::SteamAPI_ISteamUtils_StartVRDashboard(self.utils) }
+    }
+
+    /// Loads the text filtering dictionaries for the user's language.
+    ///
+    /// Must be called before `filter_text`. Returns whether filtering is
+    /// available.
+    pub fn init_filter_text(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_InitFilterText(self.utils, 0) }
+    }
+
+    /// Filters profanity and slurs from text written by another player,
+    /// following the preferences of the current user and the laws of their
+    /// region.
+    ///
+    /// `source` is the user that wrote the text. Text written by the current
+    /// user is never filtered.
+    pub fn filter_text(
+        &self,
+        context: TextFilteringContext,
+        source: SteamId,
+        input: &str,
+    ) -> String {
+        let input = CString::new(input).unwrap();
+        unsafe {
+            let mut buffer = vec![0; input.as_bytes_with_nul().len()];
+            sys::SteamAPI_ISteamUtils_FilterText(
+                self.utils,
+                context.into(),
+                source.0,
+                input.as_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+            );
+            let text = CStr::from_ptr(buffer.as_ptr());
+            text.to_string_lossy().into_owned()
+        }
+    }
+
+    /// Returns the remaining battery charge in percent.
+    ///
+    /// Returns `None` if the device isn't running on battery power.
+    pub fn current_battery_power(&self) -> Option<u8> {
+        unsafe {
+            match sys::SteamAPI_ISteamUtils_GetCurrentBatteryPower(self.utils) {
+                255 => None,
+                power => Some(power),
+            }
+        }
+    }
+
+    /// Returns the number of IPC calls made to the steam client since the
+    /// last call to this function.
+    ///
+    /// Calling this once per frame shows how much the game talks to steam.
+    pub fn ipc_call_count(&self) -> u32 {
+        unsafe { sys::SteamAPI_ISteamUtils_GetIPCCallCount(self.utils) }
+    }
+
+    /// Returns the number of seconds since the user last interacted with
+    /// the game
+    pub fn seconds_since_app_active(&self) -> u32 {
+        unsafe { sys::SteamAPI_ISteamUtils_GetSecondsSinceAppActive(self.utils) }
+    }
+
+    /// Returns the number of seconds since the user last interacted with
+    /// the computer
+    pub fn seconds_since_computer_active(&self) -> u32 {
+        unsafe { sys::SteamAPI_ISteamUtils_GetSecondsSinceComputerActive(self.utils) }
+    }
+
+    /// Returns whether the steam overlay is enabled for the game.
+    ///
+    /// The overlay can take a few seconds to start, and some features like
+    /// invites don't work without it.
+    pub fn is_overlay_enabled(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_IsOverlayEnabled(self.utils) }
+    }
+
+    /// Returns whether the overlay needs the game to present a frame.
+    ///
+    /// Games that only render when something changes should keep presenting
+    /// frames while this returns `true` so that the overlay stays responsive.
+    pub fn overlay_needs_present(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_BOverlayNeedsPresent(self.utils) }
+    }
+
+    /// Returns whether the given api call has completed, successfully or not.
+    ///
+    /// This is only needed when managing api call handles manually, the
+    /// crate's own asynchronous methods are completed by `run_callbacks`.
+    pub fn is_api_call_completed(&self, call: ApiCall) -> bool {
+        unsafe {
+            let mut failed = false;
+            sys::SteamAPI_ISteamUtils_IsAPICallCompleted(self.utils, call.0, &mut failed)
+        }
+    }
+
+    /// Returns why the given api call failed, or `None` if it didn't fail
+    pub fn api_call_failure_reason(&self, call: ApiCall) -> Option<ApiCallFailure> {
+        unsafe {
+            ApiCallFailure::from_raw(sys::SteamAPI_ISteamUtils_GetAPICallFailureReason(
+                self.utils, call.0,
+            ))
+        }
+    }
+
+    /// Reads the result of the given api call as `T`.
+    ///
+    /// Returns `None` if the call hasn't completed yet. The result can only
+    /// be read once, later calls fail with `ApiCallFailure::InvalidHandle`.
+    pub fn get_api_call_result<T: Callback>(
+        &self,
+        call: ApiCall,
+    ) -> Option<Result<T, ApiCallFailure>> {
+        unsafe {
+            if !self.is_api_call_completed(call) {
+                return None;
+            }
+            // Allocated as u64s to meet the alignment of any result struct
+            let mut buffer = vec![0u64; (T::SIZE as usize).div_ceil(8)];
+            let mut failed = false;
+            let ok = sys::SteamAPI_ISteamUtils_GetAPICallResult(
+                self.utils,
+                call.0,
+                buffer.as_mut_ptr() as *mut _,
+                T::SIZE,
+                T::ID,
+                &mut failed,
+            );
+            if ok && !failed {
+                Some(Ok(T::from_raw(buffer.as_mut_ptr() as *mut _)))
+            } else {
+                Some(Err(self
+                    .api_call_failure_reason(call)
+                    .unwrap_or(ApiCallFailure::InvalidHandle)))
+            }
+        }
+    }
+
     /// Sets the position on the screen where popups from the steam overlay
     /// should appear and display themselves in.
     pub fn set_overlay_notification_position(&self, position: NotificationPosition) {
@@ -100,6 +465,16 @@ impl<Manager> Utils<Manager> {
         }
     }
 
+    /// Sets how far overlay notifications are moved away from the corner
+    /// set with `set_overlay_notification_position`, in pixels.
+    ///
+    /// Useful to keep notifications from covering HUD elements.
+    pub fn set_overlay_notification_inset(&self, horizontal: i32, vertical: i32) {
+        unsafe {
+            sys::SteamAPI_ISteamUtils_SetOverlayNotificationInset(self.utils, horizontal, vertical);
+        }
+    }
+
     /// Sets the Steam warning callback, which is called to emit warning messages.
     ///
     /// The passed-in function takes two arguments: a severity level (0 = info, 1 = warning) and
@@ -118,6 +493,101 @@ impl<Manager> Utils<Manager> {
             sys::SteamAPI_ISteamUtils_SetWarningMessageHook(self.utils, Some(c_warning_callback));
         }
     }
+
+    /// Sets a Steam warning callback that forwards messages to the `log` crate.
+    ///
+    /// Info messages are logged at the `info` level and warnings at the `warn`
+    /// level, with `steamworks` as the target. Launch the game with
+    /// `-debug_steamapi` to get more messages.
+    #[cfg(feature = "log")]
+    pub fn forward_warnings_to_log(&self) {
+        self.set_warning_callback(|level, msg| {
+            let level = if level == 0 {
+                log::Level::Info
+            } else {
+                log::Level::Warn
+            };
+            log::log!(target: "steamworks", level, "{}", msg.to_string_lossy());
+        });
+    }
+
+    /// Opens a full screen text input dialog for users playing with a
+    /// gamepad, e.g. on the Steam Deck or in Big Picture mode.
+    ///
+    /// Returns whether the dialog was shown. Once the user closes it a
+    /// `GamepadTextInputDismissed` callback is sent, after which the text
+    /// can be read with `entered_gamepad_text_input`.
+    pub fn show_gamepad_text_input(
+        &self,
+        mode: GamepadTextInputMode,
+        line_mode: GamepadTextInputLineMode,
+        description: &str,
+        max_chars: u32,
+        existing_text: &str,
+    ) -> bool {
+        let description = CString::new(description).unwrap();
+        let existing_text = CString::new(existing_text).unwrap();
+        unsafe {
+            sys::SteamAPI_ISteamUtils_ShowGamepadTextInput(
+                self.utils,
+                mode.into(),
+                line_mode.into(),
+                description.as_ptr(),
+                max_chars,
+                existing_text.as_ptr(),
+            )
+        }
+    }
+
+    /// Opens an on-screen keyboard that doesn't cover the given text field,
+    /// e.g. a chat box, for users playing on the Steam Deck.
+    ///
+    /// The position and size of the text field are in screen pixels. Typed
+    /// text is sent to the game as regular keyboard input. Returns whether
+    /// the keyboard was shown; a `FloatingGamepadTextInputDismissed`
+    /// callback is sent once it is closed.
+    pub fn show_floating_gamepad_text_input(
+        &self,
+        mode: FloatingGamepadTextInputMode,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> bool {
+        unsafe {
+            sys::SteamAPI_ISteamUtils_ShowFloatingGamepadTextInput(
+                self.utils,
+                mode.into(),
+                x,
+                y,
+                width,
+                height,
+            )
+        }
+    }
+
+    /// Closes the keyboard opened with `show_floating_gamepad_text_input`
+    pub fn dismiss_floating_gamepad_text_input(&self) -> bool {
+        unsafe { sys::SteamAPI_ISteamUtils_DismissFloatingGamepadTextInput(self.utils) }
+    }
+
+    /// Returns the text the user submitted in the last gamepad text input dialog
+    pub fn entered_gamepad_text_input(&self) -> Option<String> {
+        unsafe {
+            let len = sys::SteamAPI_ISteamUtils_GetEnteredGamepadTextLength(self.utils);
+            let mut buffer = vec![0; len as usize + 1];
+            if sys::SteamAPI_ISteamUtils_GetEnteredGamepadTextInput(
+                self.utils,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+            ) {
+                let text = CStr::from_ptr(buffer.as_ptr());
+                Some(text.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        }
+    }
 }
 
 pub(crate) struct SteamParamStringArray(Vec<*mut i8>);
@@ -148,3 +618,65 @@ impl SteamParamStringArray {
         }
     }
 }
+
+/// Called when the user closes a gamepad text input dialog opened with
+/// `Utils::show_gamepad_text_input`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GamepadTextInputDismissed {
+    /// The length of the submitted text in bytes, or `None` if the user
+    /// cancelled the dialog
+    pub submitted_text_len: Option<u32>,
+}
+
+unsafe impl Callback for GamepadTextInputDismissed {
+    const ID: i32 = CALLBACK_BASE_ID + 14;
+    const SIZE: i32 = ::std::mem::size_of::<sys::GamepadTextInputDismissed_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::GamepadTextInputDismissed_t);
+        GamepadTextInputDismissed {
+            submitted_text_len: if val.m_bSubmitted {
+                Some(val.m_unSubmittedText)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// Called when the keyboard opened with `Utils::show_floating_gamepad_text_input`
+/// is closed.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FloatingGamepadTextInputDismissed;
+
+unsafe impl Callback for FloatingGamepadTextInputDismissed {
+    const ID: i32 = CALLBACK_BASE_ID + 38;
+    const SIZE: i32 = ::std::mem::size_of::<sys::FloatingGamepadTextInputDismissed_t>() as i32;
+
+    unsafe fn from_raw(_: *mut c_void) -> Self {
+        FloatingGamepadTextInputDismissed
+    }
+}
+
+/// Called every minute once the battery of a device running on battery power
+/// is nearly empty, e.g. to auto-save the game.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LowBatteryPower {
+    /// The estimated number of minutes until the battery is empty
+    pub minutes_left: u8,
+}
+
+unsafe impl Callback for LowBatteryPower {
+    const ID: i32 = CALLBACK_BASE_ID + 2;
+    const SIZE: i32 = ::std::mem::size_of::<sys::LowBatteryPower_t>() as i32;
+
+    unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let val = &mut *(raw as *mut sys::LowBatteryPower_t);
+        LowBatteryPower {
+            minutes_left: val.m_nMinutesBatteryLeft,
+        }
+    }
+}
@@ -58,6 +58,51 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rustc-link-search={}", out_path.display());
     println!("cargo:rustc-link-lib=dylib={}", lib);
 
+    // The ticket library isn't redistributable, it ships with the sdk for
+    // use on backend servers only, so it isn't bundled with this crate.
+    if env::var_os("CARGO_FEATURE_ENCRYPTED_APP_TICKET").is_some() {
+        let mut ticket_lib = "sdkencryptedappticket";
+        let mut ticket_path = sdk_loc.join("public/steam/lib");
+        if triple.contains("windows") {
+            if triple.contains("i686") {
+                ticket_path.push("win32");
+            } else {
+                ticket_lib = "sdkencryptedappticket64";
+                ticket_path.push("win64");
+            }
+        } else if triple.contains("linux") {
+            if triple.contains("i686") {
+                ticket_path.push("linux32");
+            } else {
+                ticket_path.push("linux64");
+            }
+        } else {
+            ticket_path.push("osx");
+        }
+
+        let ticket_files = if triple.contains("windows") {
+            vec![format!("{}.dll", ticket_lib), format!("{}.lib", ticket_lib)]
+        } else if triple.contains("darwin") {
+            vec![format!("lib{}.dylib", ticket_lib)]
+        } else {
+            vec![format!("lib{}.so", ticket_lib)]
+        };
+        for file in ticket_files {
+            let from = ticket_path.join(&file);
+            if !from.exists() {
+                return Err(format!(
+                    "the `encrypted-app-ticket` feature needs {}, which is only part of \
+                     the full Steamworks SDK. Set STEAM_SDK_LOCATION to the `sdk` folder \
+                     of the SDK download.",
+                    from.display()
+                )
+                .into());
+            }
+            fs::copy(from, out_path.join(file))?;
+        }
+        println!("cargo:rustc-link-lib=dylib={}", ticket_lib);
+    }
+
     #[cfg(feature = "rebuild-bindings")]
     {
         let target_os = if triple.contains("windows") {
@@ -0,0 +1,61 @@
+// Declarations from `public/steam/steamencryptedappticket.h`, which isn't
+// part of the flat api the rest of the bindings are generated from.
+
+pub const k_nSteamEncryptedAppTicketSymmetricKeyLen: ::std::os::raw::c_int = 32;
+
+extern "C" {
+    pub fn SteamEncryptedAppTicket_BDecryptTicket(
+        rgubTicketEncrypted: *const uint8,
+        cubTicketEncrypted: uint32,
+        rgubTicketDecrypted: *mut uint8,
+        pcubTicketDecrypted: *mut uint32,
+        rgubKey: *const uint8,
+        cubKey: ::std::os::raw::c_int,
+    ) -> bool;
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_BIsTicketForApp(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+        nAppID: AppId_t,
+    ) -> bool;
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_GetTicketIssueTime(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+    ) -> RTime32;
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_GetTicketSteamID(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+        psteamID: *mut CSteamID,
+    );
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_GetTicketAppID(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+    ) -> AppId_t;
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_BUserOwnsAppInTicket(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+        nAppID: AppId_t,
+    ) -> bool;
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_BUserIsVacBanned(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+    ) -> bool;
+}
+extern "C" {
+    pub fn SteamEncryptedAppTicket_GetUserVariableData(
+        rgubTicketDecrypted: *mut uint8,
+        cubTicketDecrypted: uint32,
+        pcubUserData: *mut uint32,
+    ) -> *const uint8;
+}
@@ -10,3 +10,6 @@ include!("macos_bindings.rs");
 
 #[cfg(target_os = "linux")]
 include!("linux_bindings.rs");
+
+#[cfg(feature = "encrypted-app-ticket")]
+include!("encrypted_app_ticket.rs");